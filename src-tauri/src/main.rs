@@ -5,15 +5,156 @@ use tauri::{
     CustomMenuItem, GlobalShortcutManager, Manager, SystemTray, SystemTrayEvent,
     SystemTrayMenu, SystemTrayMenuItem,
 };
-use std::sync::Mutex;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 use std::path::PathBuf;
 
+mod providers;
+
+use providers::cache::{CacheEntryInfo, CacheKey, CacheStats, TranslationCache};
+use providers::http::{HttpPool, NetworkSettings};
+use providers::langdetect::LabeledSegment;
+use providers::rate_limit::{RateLimitConfig, RateLimited, RateLimiter};
+use providers::routing::{ResolvedDirection, RoutingRules};
+use providers::{
+    CallError, ChunkSink, DetectedLanguage, ProviderInfo, ProviderRegistry, ProviderSettings, SupportedLanguage,
+    TranslationOutcome, TranslationProvider, TranslationRequest,
+};
+
 // Global state to store clipboard content
 struct ClipboardState(Mutex<String>);
 
 // Global state to store current hotkey
 struct HotkeyState(Mutex<String>);
 
+// Global state of the translation pipeline, managed once in `setup`
+struct AppState {
+    providers: ProviderRegistry,
+    // Pooled HTTP client shared by all providers
+    http: Arc<HttpPool>,
+    // Client-side per-provider rate limits
+    limits: Arc<RateLimiter>,
+    // Translation cache (memory LRU + app data dir)
+    cache: Arc<TranslationCache>,
+    // Language-pair routing rules (pushed from the settings)
+    routing: Mutex<RoutingRules>,
+    jobs: TranslationJobs,
+    // Emits streamed chunks (`translation-chunk`) to the windows
+    app_handle: tauri::AppHandle,
+}
+
+impl AppState {
+    fn provider(&self, provider_id: &str) -> Result<Arc<dyn TranslationProvider>, String> {
+        self.providers
+            .get(provider_id)
+            .ok_or_else(|| format!("Unknown translation provider: {}", provider_id))
+    }
+}
+
+// In-flight translations by request id so they can be aborted
+#[derive(Default)]
+struct TranslationJobs(Mutex<HashMap<String, tokio::task::AbortHandle>>);
+
+impl TranslationJobs {
+    fn track(&self, request_id: &str, handle: tokio::task::AbortHandle) {
+        match self.0.lock() {
+            Ok(mut jobs) => {
+                // A reused id supersedes the earlier request
+                if let Some(previous) = jobs.insert(request_id.to_string(), handle) {
+                    previous.abort();
+                }
+            }
+            Err(e) => eprintln!("Failed to lock translation jobs: {}", e),
+        }
+    }
+
+    fn finish(&self, request_id: &str) {
+        if let Ok(mut jobs) = self.0.lock() {
+            jobs.remove(request_id);
+        }
+    }
+
+    // Returns false when the request already finished (or never existed)
+    fn cancel(&self, request_id: &str) -> bool {
+        match self.0.lock() {
+            Ok(mut jobs) => match jobs.remove(request_id) {
+                Some(handle) => {
+                    println!("Cancelling translation {}", request_id);
+                    handle.abort();
+                    true
+                }
+                None => false,
+            },
+            Err(e) => {
+                eprintln!("Failed to lock translation jobs: {}", e);
+                false
+            }
+        }
+    }
+
+    // Abort everything in flight (a new hotkey press supersedes all running translations)
+    fn cancel_all(&self) {
+        if let Ok(mut jobs) = self.0.lock() {
+            if !jobs.is_empty() {
+                println!("Cancelling {} superseded translation(s)", jobs.len());
+            }
+            for (_, handle) in jobs.drain() {
+                handle.abort();
+            }
+        }
+    }
+}
+
+// Error returned by translation commands, serialized as `{ kind, message, ... }` so the frontend
+// can tell a locally rate-limited request (never sent) from a failed one
+#[derive(Debug, serde::Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum TranslateError {
+    RateLimited {
+        provider_id: String,
+        retry_after_ms: Option<u64>,
+        message: String,
+    },
+    Failed {
+        message: String,
+    },
+}
+
+impl From<String> for TranslateError {
+    fn from(message: String) -> Self {
+        TranslateError::Failed { message }
+    }
+}
+
+impl From<CallError> for TranslateError {
+    fn from(e: CallError) -> Self {
+        match e {
+            CallError::RateLimited(limited) => limited.into(),
+            CallError::Failed(message) => message.into(),
+        }
+    }
+}
+
+impl From<RateLimited> for TranslateError {
+    fn from(e: RateLimited) -> Self {
+        TranslateError::RateLimited {
+            message: e.to_string(),
+            provider_id: e.provider_id,
+            retry_after_ms: e.retry_after_ms,
+        }
+    }
+}
+
+// Payload of the `translation-chunk` event emitted while a provider streams its output
+#[derive(Clone, serde::Serialize)]
+struct TranslationChunk {
+    request_id: String,
+    provider_id: String,
+    delta: String,
+    // Full translation received so far
+    text: String,
+}
+
 // Utility: safely truncate a &str by character count to avoid UTF-8 boundary panics
 fn truncate_chars(s: &str, max_chars: usize) -> String {
     s.chars().take(max_chars).collect::<String>()
@@ -65,449 +206,359 @@ async fn get_selected_text_command() -> Result<String, String> {
 }
 
 #[tauri::command]
-async fn translate_text(text: String, target_lang: String, source_lang: String) -> Result<String, String> {
-    println!("=== translate_text called ===");
+async fn list_providers(app: tauri::State<'_, AppState>) -> Result<Vec<ProviderInfo>, String> {
+    Ok(app.providers.list())
+}
+
+#[tauri::command]
+async fn translate(
+    provider_id: String,
+    text: String,
+    source_lang: String,
+    target_lang: String,
+    settings: Option<ProviderSettings>,
+    request_id: Option<String>,
+    app: tauri::State<'_, AppState>,
+) -> Result<TranslationOutcome, TranslateError> {
+    let started = std::time::Instant::now();
+    println!("=== translate called ({}) ===", provider_id);
     println!("Text: {}", redact_text(&text));
     println!("Source lang: {}", source_lang);
     println!("Target lang: {}", target_lang);
 
-    // Google Translate Web API (no API key required, same as pot-app)
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(10))
-        .build()
-        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
-    let url = "https://translate.google.com/translate_a/single";
-
-    println!("Sending request to Google Translate...");
-
-    match client.get(url)
-        .query(&[
-            ("client", "gtx"),
-            ("sl", &source_lang),
-            ("tl", &target_lang),
-            ("hl", &target_lang),
-            ("dt", "t"),
-            ("dt", "bd"),
-            ("dj", "1"),
-            ("source", "input"),
-            ("q", &text),
-        ])
-        .send()
-        .await
-    {
-        Ok(response) => {
-            let status = response.status();
-            println!("Response status: {}", status);
-
-            if response.status().is_success() {
-                match response.json::<serde_json::Value>().await {
-                    Ok(json) => {
-                        if is_verbose() {
-                            println!("Received JSON response");
-                            println!("JSON: {}", serde_json::to_string_pretty(&json).unwrap_or_default());
-                        } else {
-                            println!("Received JSON response (details hidden in non-verbose mode)");
-                        }
+    let provider = app.provider(&provider_id)?;
+    let settings = settings.unwrap_or_default();
+    app.http.remember_origin(&provider.base_url(&settings));
+
+    let request = TranslationRequest {
+        text,
+        source_lang,
+        target_lang,
+        settings,
+        chunk_sink: request_id.as_deref().map(|id| chunk_sink(&app.app_handle, id, &provider_id)),
+        limiter: Some(app.limits.clone()),
+    };
 
-                        if let Some(sentences) = json["sentences"].as_array() {
-                            println!("Found {} sentences", sentences.len());
-                            let mut result = String::new();
-                            for sentence in sentences {
-                                if let Some(trans) = sentence["trans"].as_str() {
-                                    if is_verbose() { println!("Translation part: {}", trans); }
-                                    result.push_str(trans);
-                                }
-                            }
-                            if !result.is_empty() {
-                                if is_verbose() { println!("Final translation: {}", result); }
-                                return Ok(result);
-                            }
-                            println!("No translation found in sentences");
-                        } else {
-                            println!("No 'sentences' field in JSON");
-                        }
-                        Err("Translation not found in response".to_string())
-                    }
-                    Err(e) => {
-                        println!("Failed to parse JSON: {}", e);
-                        Err(format!("Failed to parse response: {}", e))
-                    }
-                }
-            } else {
-                println!("Request failed with status: {}", status);
-                Err(format!("API request failed with status: {}", response.status()))
-            }
-        }
-        Err(e) => {
-            println!("Request error: {}", e);
-            if e.is_timeout() {
-                Err("翻訳リクエストがタイムアウトしました。インターネット接続を確認してください。".to_string())
-            } else if e.is_connect() {
-                Err("Google翻訳に接続できませんでした。オフラインの可能性があります。".to_string())
-            } else {
-                Err(format!("リクエストに失敗しました: {}", e))
-            }
-        }
+    let key = CacheKey::new(&provider_id, &request);
+    if let Some(outcome) = app.cache.get(&key) {
+        return Ok(outcome.with_latency(started));
     }
+
+    let cache = app.cache.clone();
+    run_tracked(&app.jobs, request_id, async move {
+        let outcome = providers::mixed::translate_mixed(provider.as_ref(), &request).await?;
+        cache.put(key, &outcome);
+        Ok(outcome)
+    })
+    .await
+    .map(|outcome| outcome.with_latency(started))
 }
 
+// Translate with an ordered list of providers, falling back to the next one on failure
 #[tauri::command]
-async fn get_gemini_models(api_key: String) -> Result<Vec<String>, String> {
-    println!("Fetching Gemini models with key length: {}", api_key.len());
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(10))
-        .build()
-        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
-    // Correct endpoint for listing models
-    let url = format!("https://generativelanguage.googleapis.com/v1beta/models?key={}", api_key);
-
-    println!("Request URL: {}", url.replace(&api_key, "***"));
-
-    match client.get(&url).send().await {
-        Ok(response) => {
-            let status = response.status();
-            println!("Response status: {}", status);
-
-            // Get response body as text for debugging
-            match response.text().await {
-                Ok(body) => {
-                    if is_verbose() {
-                        let preview = truncate_chars(&body, 500);
-                        println!("Response body (first 500 chars): {}", preview);
-                    } else {
-                        println!("Gemini models response received (body hidden in non-verbose mode)");
-                    }
+async fn translate_with_fallback(
+    provider_ids: Vec<String>,
+    text: String,
+    source_lang: String,
+    target_lang: String,
+    settings: Option<HashMap<String, ProviderSettings>>,
+    request_id: Option<String>,
+    app: tauri::State<'_, AppState>,
+) -> Result<TranslationOutcome, TranslateError> {
+    let started = std::time::Instant::now();
+    println!("=== translate_with_fallback called ({}) ===", provider_ids.join(" -> "));
+    println!("Text: {}", redact_text(&text));
+    println!("Source lang: {}", source_lang);
+    println!("Target lang: {}", target_lang);
 
-                    if status.is_success() {
-                        match serde_json::from_str::<serde_json::Value>(&body) {
-                            Ok(json) => {
-                                println!("Successfully parsed JSON");
-                                let mut models = Vec::new();
-                                if let Some(model_list) = json["models"].as_array() {
-                                    println!("Found {} models in response", model_list.len());
-                                    for model in model_list {
-                                        if let Some(name) = model["name"].as_str() {
-                                            // Extract model name from "models/gemini-xxx" format
-                                            if let Some(model_name) = name.strip_prefix("models/") {
-                                                println!("Adding model: {}", model_name);
-                                                models.push(model_name.to_string());
-                                            }
-                                        }
-                                    }
-                                } else {
-                                    println!("No 'models' array found in response");
-                                }
-                                println!("Returning {} models", models.len());
-                                Ok(models)
-                            }
-                            Err(e) => {
-                                let err_msg = if is_verbose() {
-                                    format!("Failed to parse JSON: {}", e)
-                                } else {
-                                    "Failed to parse JSON".to_string()
-                                };
-                                println!("{}", err_msg);
-                                // Return fallback models if parsing fails
-                                Ok(get_fallback_models())
-                            }
-                        }
-                    } else {
-                        let err_msg = if is_verbose() {
-                            format!("API returned error {}: {}", status, body)
-                        } else {
-                            format!("API returned error {}", status)
-                        };
-                        println!("{} - Using fallback models", err_msg);
-                        // Return fallback models if API call fails
-                        Ok(get_fallback_models())
-                    }
-                }
-                Err(e) => {
-                    let err_msg = if is_verbose() {
-                        format!("Failed to read response body: {}", e)
-                    } else {
-                        "Failed to read response body".to_string()
-                    };
-                    println!("{} - Using fallback models", err_msg);
-                    Ok(get_fallback_models())
-                }
-            }
+    let mut settings = settings.unwrap_or_default();
+    let chain = provider_ids
+        .iter()
+        .map(|provider_id| {
+            let provider = app.provider(provider_id)?;
+            let settings = settings.remove(provider_id).unwrap_or_default();
+            app.http.remember_origin(&provider.base_url(&settings));
+            let request = TranslationRequest {
+                text: text.clone(),
+                source_lang: source_lang.clone(),
+                target_lang: target_lang.clone(),
+                settings,
+                chunk_sink: request_id.as_deref().map(|id| chunk_sink(&app.app_handle, id, provider_id)),
+                limiter: Some(app.limits.clone()),
+            };
+            Ok((provider, request))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let cache = app.cache.clone();
+    run_tracked(&app.jobs, request_id, async move {
+        Ok(providers::fallback::translate_with_fallback(chain, cache).await?)
+    })
+    .await
+    .map(|outcome| outcome.with_latency(started))
+}
+
+// Streaming providers report partial output, tagged with the request id (windows only pick up
+// the ids they sent)
+fn chunk_sink(app_handle: &tauri::AppHandle, request_id: &str, provider_id: &str) -> ChunkSink {
+    let app_handle = app_handle.clone();
+    let request_id = request_id.to_string();
+    let provider_id = provider_id.to_string();
+    ChunkSink::new(move |delta, text| {
+        let payload = TranslationChunk {
+            request_id: request_id.clone(),
+            provider_id: provider_id.clone(),
+            delta: delta.to_string(),
+            text: text.to_string(),
+        };
+        if let Err(e) = app_handle.emit_all("translation-chunk", payload) {
+            eprintln!("Failed to emit translation-chunk: {}", e);
         }
-        Err(e) => {
-            let err_msg = format!("Request failed: {}", e);
-            println!("{} - Using fallback models", err_msg);
-            Ok(get_fallback_models())
+    })
+}
+
+// Run a translation; with a request id it runs in its own task so `cancel_translation` can abort it
+async fn run_tracked(
+    jobs: &TranslationJobs,
+    request_id: Option<String>,
+    translation: impl std::future::Future<Output = Result<TranslationOutcome, TranslateError>> + Send + 'static,
+) -> Result<TranslationOutcome, TranslateError> {
+    // Without a request id the caller cannot cancel, so there is nothing to track
+    let Some(request_id) = request_id else {
+        return translation.await;
+    };
+
+    let task = tokio::spawn(translation);
+    jobs.track(&request_id, task.abort_handle());
+    let result = task.await;
+    jobs.finish(&request_id);
+
+    match result {
+        Ok(outcome) => outcome,
+        Err(e) if e.is_cancelled() => {
+            println!("Translation {} was cancelled", request_id);
+            Err("翻訳がキャンセルされました".to_string().into())
         }
+        Err(e) => Err(format!("Translation task failed: {}", e).into()),
     }
 }
 
-// Fallback list of commonly used Gemini models
-fn get_fallback_models() -> Vec<String> {
-    vec![
-        "gemini-2.0-flash-exp".to_string(),
-        "gemini-2.0-flash".to_string(),
-        "gemini-1.5-flash".to_string(),
-        "gemini-1.5-flash-8b".to_string(),
-        "gemini-1.5-pro".to_string(),
-        "gemini-pro".to_string(),
-    ]
+#[tauri::command]
+async fn cancel_translation(
+    request_id: String,
+    app: tauri::State<'_, AppState>,
+) -> Result<bool, String> {
+    Ok(app.jobs.cancel(&request_id))
 }
 
 #[tauri::command]
-async fn get_latest_flash_model(api_key: String) -> Result<String, String> {
-    let models = get_gemini_models(api_key).await?;
-
-    // Filter flash models and sort to get the latest
-    let mut flash_models: Vec<String> = models
-        .into_iter()
-        .filter(|m| m.contains("flash") && m.contains("gemini"))
-        .collect();
+async fn list_models(
+    provider_id: String,
+    settings: Option<ProviderSettings>,
+    app: tauri::State<'_, AppState>,
+) -> Result<Vec<String>, String> {
+    let provider = app.provider(&provider_id)?;
+    provider.list_models(&settings.unwrap_or_default()).await
+}
 
-    if flash_models.is_empty() {
-        return Err("No flash models found".to_string());
-    }
+#[tauri::command]
+async fn detect_with_provider(
+    provider_id: String,
+    text: String,
+    settings: Option<ProviderSettings>,
+    app: tauri::State<'_, AppState>,
+) -> Result<Vec<DetectedLanguage>, TranslateError> {
+    let provider = app.provider(&provider_id)?;
+    let _permit = app.limits.acquire(&provider_id).await?;
+    Ok(provider.detect(&text, &settings.unwrap_or_default()).await?)
+}
 
-    // Sort to get the latest version (e.g., gemini-2.5-flash is newer than gemini-1.5-flash)
-    flash_models.sort_by(|a, b| b.cmp(a)); // Descending order
+#[tauri::command]
+async fn list_languages(
+    provider_id: String,
+    settings: Option<ProviderSettings>,
+    app: tauri::State<'_, AppState>,
+) -> Result<Vec<SupportedLanguage>, String> {
+    let provider = app.provider(&provider_id)?;
+    provider.supported_languages(&settings.unwrap_or_default()).await
+}
 
-    Ok(flash_models[0].clone())
+// Model list for the Gemini settings (same as `list_models` with only a key and base URL)
+#[tauri::command]
+async fn get_gemini_models(
+    api_key: String,
+    base_url: Option<String>,
+    app: tauri::State<'_, AppState>,
+) -> Result<Vec<String>, String> {
+    let provider = app.provider("gemini")?;
+    let settings = ProviderSettings { api_key: Some(api_key), base_url, ..Default::default() };
+    provider.list_models(&settings).await
 }
 
-use serde::{Serialize, Deserialize};
+#[tauri::command]
+async fn get_clipboard_text() -> Result<String, String> {
+    use clipboard::{ClipboardContext, ClipboardProvider};
 
-#[derive(Serialize, Deserialize)]
-struct GeminiTranslationResult {
-    translated_text: String,
-    model_used: String,
+    let mut ctx: ClipboardContext = ClipboardProvider::new()
+        .map_err(|e| format!("Failed to get clipboard context: {}", e))?;
+
+    ctx.get_contents()
+        .map_err(|e| format!("Failed to get clipboard contents: {}", e))
 }
 
 #[tauri::command]
-async fn translate_with_gemini(text: String, target_lang: String, api_key: String, model: Option<String>) -> Result<GeminiTranslationResult, String> {
-    println!("=== Gemini Translation Start ===");
-    println!("Text: {}", redact_text(&text));
-    println!("Target Lang: {}", target_lang);
-    println!("API Key length: {}", api_key.len());
-    println!("Model: {:?}", model);
-
-    // Use provided model or get the latest flash model
-    let model_name = match model {
-        Some(m) => {
-            println!("Using provided model: {}", m);
-            m
-        },
-        None => {
-            println!("Getting latest flash model...");
-            match get_latest_flash_model(api_key.clone()).await {
-                Ok(m) => {
-                    println!("Latest flash model: {}", m);
-                    m
-                },
-                Err(e) => {
-                    println!("Failed to get latest flash model: {}", e);
-                    return Err(e);
-                }
-            }
+async fn detect_language(
+    text: String,
+    base_url: Option<String>,
+    allow_network: Option<bool>,
+    app: tauri::State<'_, AppState>,
+) -> Result<String, TranslateError> {
+    let allow_network = allow_network.unwrap_or(true);
+    let (language, _) = detect_source_language(&text, base_url.as_deref(), allow_network, &app).await?;
+    Ok(language)
+}
+
+// The detected language, and whether it is only a low-confidence local guess (network detection
+// failed or was not allowed). `allow_network` is false when the free Google endpoint is disabled:
+// the selection must then not be sent to translate.google.com, even for detection
+async fn detect_source_language(
+    text: &str,
+    base_url: Option<&str>,
+    allow_network: bool,
+    app: &AppState,
+) -> Result<(String, bool), TranslateError> {
+    // The local detector settles most cases (kana, Hangul, plain English) without a round trip
+    let local = providers::langdetect::detect(text);
+    if let Some(best) = local.first().filter(|d| d.confidence >= providers::langdetect::CONFIDENT) {
+        println!("Detected language locally: {} ({:.2})", best.language, best.confidence);
+        return Ok((best.language.clone(), false));
+    }
+
+    let base_url = providers::resolve_base_url("google", base_url, providers::google::DEFAULT_BASE_URL);
+    app.http.remember_origin(&base_url);
+    let network = async {
+        if !allow_network {
+            return Err(TranslateError::from("Google翻訳（Web）が無効なため言語を検出できません".to_string()));
         }
+        // Detection hits the same Google web endpoint as translation, so it shares its budget
+        let _permit = app.limits.acquire("google").await?;
+        Ok::<_, TranslateError>(providers::google::detect_language(&app.http, &base_url, text).await?)
     };
+    match (network.await, local.into_iter().next()) {
+        (Ok(language), _) => Ok((language, false)),
+        // Offline or rate limited: a low-confidence local guess beats no direction at all, but the
+        // caller may still try another detector
+        (Err(e), Some(best)) => {
+            println!("Network detection failed ({:?}); using local guess {} ({:.2})", e, best.language, best.confidence);
+            Ok((best.language, true))
+        }
+        (Err(e), None) => Err(e),
+    }
+}
 
-    println!("Final model name: {}", model_name);
+// Source and target language for `text` according to the routing rules. `source_lang` skips
+// detection when the caller already knows the language (e.g. from another detector).
+#[tauri::command]
+async fn resolve_direction(
+    text: String,
+    source_lang: Option<String>,
+    base_url: Option<String>,
+    allow_network: Option<bool>,
+    app: tauri::State<'_, AppState>,
+) -> Result<ResolvedDirection, String> {
+    let (detected, low_confidence) = match source_lang {
+        Some(language) => (Some(language), false),
+        None => match detect_source_language(&text, base_url.as_deref(), allow_network.unwrap_or(true), &app).await {
+            Ok((language, low_confidence)) => (Some(language), low_confidence),
+            Err(e) => {
+                eprintln!("Language detection failed; using the default direction: {:?}", e);
+                (None, false)
+            }
+        },
+    };
 
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(15))
-        .build()
-        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
-    let url = format!(
-        "https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent?key={}",
-        model_name, api_key
+    let rules = app.routing.lock().map_err(|e| format!("Failed to lock routing rules: {}", e))?;
+    let mut direction = rules.resolve(detected.as_deref());
+    direction.low_confidence = low_confidence && direction.detected_language.is_some();
+    println!(
+        "Resolved direction: {} -> {} (rule: {:?}, reversed: {}, low confidence: {})",
+        direction.source_lang, direction.target_lang, direction.rule_index, direction.reversed, direction.low_confidence
     );
+    Ok(direction)
+}
 
-    println!("Request URL: {}", url.replace(&api_key, "***"));
-
-    // System-style instruction to improve translation quality and preserve formatting/placeholders
-    let system_instruction = format!(
-        concat!(
-            "You are a professional translation engine. Translate the user-provided text into {} only.\n",
-            "Constraints:\n",
-            "- Preserve original formatting, line breaks, markdown, code blocks, and list structure.\n",
-            "- Keep placeholders and variables untouched (e.g., {{like_this}}, {{{{curly}}}}, %s, %d, {{{{name}}}}, <tag>, URLs, and file paths).\n",
-            "- Do not add explanations or commentary. Output only the translated text.\n",
-            "- Maintain numbers, units, punctuation, emojis, and inline symbols.\n",
-            "- If the text is mostly code or untranslatable terms, keep them as-is and translate surrounding prose naturally.\n",
-            "- Prefer concise, natural, context-appropriate wording.\n"
-        ),
-        target_lang
-    );
+// Replace the routing rules (called on startup and when settings are saved)
+#[tauri::command]
+async fn set_routing_rules(
+    rules: RoutingRules,
+    app: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    let mut current = app.routing.lock().map_err(|e| format!("Failed to lock routing rules: {}", e))?;
+    *current = rules;
+    Ok(())
+}
 
-    let prompt = format!(
-        "{}\n\nText to translate:\n{}",
-        system_instruction,
-        text
-    );
+// Language of each sentence / line, for mixed-language text (no network)
+#[tauri::command]
+async fn detect_segments(text: String) -> Result<Vec<LabeledSegment>, String> {
+    Ok(providers::langdetect::detect_segments(&text))
+}
 
-    let params = serde_json::json!({
-        "contents": [
-            {
-                "parts": [
-                    {
-                        "text": prompt
-                    }
-                ]
-            }
-        ]
-    });
+// Ranked local detection only (no network)
+#[tauri::command]
+async fn detect_language_local(text: String) -> Result<Vec<DetectedLanguage>, String> {
+    Ok(providers::langdetect::detect(&text))
+}
 
-    if is_verbose() {
-        println!("Request params: {}", serde_json::to_string(&params).unwrap_or_default());
-    } else {
-        println!("Sending Gemini request (params hidden in non-verbose mode)");
-    }
+// Apply proxy / extra CA settings to the shared client (called on startup and when settings are saved)
+#[tauri::command]
+async fn set_network_settings(
+    settings: NetworkSettings,
+    app: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    app.http.configure(&settings)
+}
 
-    match client.post(&url).json(&params).send().await {
-        Ok(response) => {
-            let status = response.status();
-            println!("Response status: {}", status);
-
-            if status.is_success() {
-                match response.text().await {
-                    Ok(body) => {
-                        if is_verbose() {
-                            let preview = truncate_chars(&body, 300);
-                            println!("Response body (first 300 chars): {}", preview);
-                        } else {
-                            println!("Gemini response received (body hidden in non-verbose mode)");
-                        }
-                        match serde_json::from_str::<serde_json::Value>(&body) {
-                            Ok(json) => {
-                                if let Some(candidates) = json["candidates"].as_array() {
-                                    if let Some(candidate) = candidates.get(0) {
-                                        if let Some(content) = candidate["content"]["parts"].as_array() {
-                                            if let Some(part) = content.get(0) {
-                                                if let Some(translated_text) = part["text"].as_str() {
-                                                    println!("Translation successful!");
-                                                    return Ok(GeminiTranslationResult {
-                                                        translated_text: translated_text.to_string(),
-                                                        model_used: model_name.clone(),
-                                                    });
-                                                }
-                                            }
-                                        }
-                                    }
-                                }
-                                let err = "Translation not found in Gemini response".to_string();
-                                println!("Error: {}", err);
-                                Err(err)
-                            }
-                            Err(e) => {
-                                let err = format!("Failed to parse Gemini response: {}", e);
-                                println!("Error: {}", err);
-                                Err(err)
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        let err = format!("Failed to read response body: {}", e);
-                        println!("Error: {}", err);
-                        Err(err)
-                    }
-                }
-            } else {
-                match response.text().await {
-                    Ok(body) => {
-                        let err = if is_verbose() {
-                            format!("Gemini API request failed with status {}: {}", status, body)
-                        } else {
-                            format!("Gemini API request failed with status {}", status)
-                        };
-                        println!("Error: {}", err);
-                        Err(err)
-                    }
-                    Err(e) => {
-                        let err = format!("Gemini API request failed with status {}: {}", status, e);
-                        println!("Error: {}", err);
-                        Err(err)
-                    }
-                }
-            }
-        }
-        Err(e) => {
-            let err = if e.is_timeout() {
-                "Gemini APIリクエストがタイムアウトしました。".to_string()
-            } else if e.is_connect() {
-                "Gemini APIに接続できませんでした。オフラインの可能性があります。".to_string()
-            } else {
-                format!("Geminiリクエストに失敗しました: {}", e)
-            };
-            println!("Error: {}", err);
-            Err(err)
-        }
-    }
+// Replace the per-provider rate limits (called on startup and when settings are saved)
+#[tauri::command]
+async fn set_rate_limits(
+    limits: HashMap<String, RateLimitConfig>,
+    app: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    app.limits.configure(limits);
+    Ok(())
 }
 
 #[tauri::command]
-async fn get_clipboard_text() -> Result<String, String> {
-    use clipboard::{ClipboardContext, ClipboardProvider};
+async fn get_cache_stats(app: tauri::State<'_, AppState>) -> Result<CacheStats, String> {
+    Ok(app.cache.stats())
+}
 
-    let mut ctx: ClipboardContext = ClipboardProvider::new()
-        .map_err(|e| format!("Failed to get clipboard context: {}", e))?;
+#[tauri::command]
+async fn list_cache_entries(
+    limit: Option<usize>,
+    app: tauri::State<'_, AppState>,
+) -> Result<Vec<CacheEntryInfo>, String> {
+    Ok(app.cache.entries(limit.unwrap_or(50)))
+}
 
-    ctx.get_contents()
-        .map_err(|e| format!("Failed to get clipboard contents: {}", e))
+// Keep translations on disk across restarts (opt-in; called on startup and when settings are saved)
+#[tauri::command]
+async fn set_cache_persistence(enabled: bool, app: tauri::State<'_, AppState>) -> Result<(), String> {
+    app.cache.set_persist(enabled)
 }
 
+// Returns the number of removed entries
 #[tauri::command]
-async fn detect_language(text: String) -> Result<String, String> {
-    // Use Google Translate Web API for language detection (no API key required)
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(8))
-        .build()
-        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
-    let url = "https://translate.google.com/translate_a/single";
-
-    match client.get(url)
-        .query(&[
-            ("client", "gtx"),
-            ("sl", "auto"),
-            ("tl", "en"),
-            ("dt", "t"),
-            ("q", &text),
-        ])
-        .send()
-        .await
-    {
-        Ok(response) => {
-            if response.status().is_success() {
-                match response.text().await {
-                    Ok(body) => {
-                        // Parse the response array to extract detected language
-                        // Response format: [[["translation",...],...],...,"detected_lang",...]
-                        if let Ok(json) = serde_json::from_str::<serde_json::Value>(&body) {
-                            if let Some(arr) = json.as_array() {
-                                // The detected language is usually at index 2
-                                if arr.len() > 2 {
-                                    if let Some(lang) = arr[2].as_str() {
-                                        return Ok(lang.to_string());
-                                    }
-                                }
-                            }
-                        }
-                        Err("Failed to detect language from response".to_string())
-                    }
-                    Err(e) => Err(format!("Failed to read response: {}", e)),
-                }
-            } else {
-                Err(format!("Detection request failed with status: {}", response.status()))
-            }
-        }
-        Err(e) => {
-            if e.is_timeout() {
-                Err("言語検出がタイムアウトしました。".to_string())
-            } else if e.is_connect() {
-                Err("言語検出に失敗しました。オフラインの可能性があります。".to_string())
-            } else {
-                Err(format!("言語検出リクエストに失敗しました: {}", e))
-            }
-        }
-    }
+async fn clear_translation_cache(app: tauri::State<'_, AppState>) -> Result<usize, String> {
+    app.cache.clear()
+}
+
+// Pre-connect to recently used endpoints while the user's selection is being read
+fn warm_up_connections(app_handle: &tauri::AppHandle) {
+    let default_url = providers::resolve_base_url("google", None, providers::google::DEFAULT_BASE_URL);
+    let warm_up = app_handle.state::<AppState>().http.warm_up(&default_url);
+    tauri::async_runtime::spawn(warm_up);
 }
 
 #[tauri::command]
@@ -536,6 +587,7 @@ async fn register_hotkey(app_handle: tauri::AppHandle, hotkey: String, state: ta
     match app_handle.global_shortcut_manager()
         .register(&hotkey, move || {
             println!("=== Global shortcut pressed ===");
+            warm_up_connections(&app_handle_clone);
 
             // Get text BEFORE showing window
             use selection::get_text;
@@ -556,7 +608,8 @@ async fn register_hotkey(app_handle: tauri::AppHandle, hotkey: String, state: ta
                     Err(e) => eprintln!("Failed to focus translate window: {}", e),
                 }
 
-                // Emit event with the text
+                // Emit event with the text (anything still running belongs to the previous selection)
+                app_handle_clone.state::<AppState>().jobs.cancel_all();
                 if let Err(e) = window.emit("translate-shortcut", text) {
                     eprintln!("Failed to emit translate-shortcut event: {}", e);
                 }
@@ -811,13 +864,27 @@ fn main() {
             _ => {}
         })
         .invoke_handler(tauri::generate_handler![
-            translate_text,
-            translate_with_gemini,
+            translate,
+            translate_with_fallback,
+            cancel_translation,
+            list_providers,
+            list_models,
+            detect_with_provider,
+            list_languages,
             get_clipboard_text,
             get_selected_text_command,
             detect_language,
+            detect_language_local,
+            detect_segments,
+            resolve_direction,
+            set_routing_rules,
             get_gemini_models,
-            get_latest_flash_model,
+            set_network_settings,
+            set_rate_limits,
+            get_cache_stats,
+            list_cache_entries,
+            clear_translation_cache,
+            set_cache_persistence,
             register_hotkey,
             get_current_hotkey,
             enable_auto_start,
@@ -826,6 +893,36 @@ fn main() {
         ])
         .setup(|app| {
             let app_handle = app.handle();
+
+            let http = Arc::new(HttpPool::new()?);
+            let registry = ProviderRegistry::with_defaults(http.clone());
+            // Offline models are installed per language pair under the app data directory
+            #[cfg(feature = "offline-nmt")]
+            let registry = {
+                let mut registry = registry;
+                match app.path_resolver().app_data_dir() {
+                    Some(dir) => registry.register(Arc::new(
+                        providers::offline::OfflineProvider::new(dir.join("models")),
+                    )),
+                    None => eprintln!("(setup) app data dir unavailable; offline translation disabled"),
+                }
+                registry
+            };
+
+            let cache_dir = app.path_resolver().app_data_dir().map(|dir| dir.join("cache").join("translations"));
+            if cache_dir.is_none() {
+                eprintln!("(setup) app data dir unavailable; translation cache can only be kept in memory");
+            }
+            app.manage(AppState {
+                providers: registry,
+                http,
+                limits: Arc::new(RateLimiter::new()),
+                cache: Arc::new(TranslationCache::new(cache_dir)),
+                routing: Mutex::new(RoutingRules::default()),
+                jobs: TranslationJobs::default(),
+                app_handle: app_handle.clone(),
+            });
+
             let state = app.state::<HotkeyState>();
             let hotkey = match state.0.lock() {
                 Ok(guard) => guard.clone(),
@@ -842,6 +939,7 @@ fn main() {
             match app.global_shortcut_manager()
                 .register(&hotkey, move || {
                     println!("=== Global shortcut {} pressed ===", hotkey_for_closure);
+                    warm_up_connections(&app_handle);
 
                     // IMPORTANT: Get text BEFORE showing window (like Pot)
                     use selection::get_text;
@@ -875,7 +973,8 @@ fn main() {
                             }
                         }
 
-                        // Emit event with the text
+                        // Emit event with the text (anything still running belongs to the previous selection)
+                        app_handle.state::<AppState>().jobs.cancel_all();
                         if let Err(e) = window.emit("translate-shortcut", text) {
                             eprintln!("Failed to emit translate-shortcut event: {}", e);
                         }
@@ -0,0 +1,217 @@
+// Anthropic Messages API (requires API key)
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    language_name, map_request_error, prompt, send_with_retry, status_error, HttpPool,
+    ProviderCapabilities, ProviderSettings, TranslationOutcome, TranslationProvider,
+    TranslationRequest,
+};
+use crate::{is_verbose, truncate_chars};
+
+const SERVICE: &str = "Anthropic API";
+const DEFAULT_BASE_URL: &str = "https://api.anthropic.com";
+const API_VERSION: &str = "2023-06-01";
+const DEFAULT_MAX_TOKENS: u32 = 4096;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct AnthropicOptions {
+    max_tokens: Option<u32>,
+}
+
+#[derive(Serialize)]
+struct MessagesRequest<'a> {
+    model: &'a str,
+    max_tokens: u32,
+    system: &'a str,
+    messages: Vec<Message<'a>>,
+    temperature: f32,
+}
+
+#[derive(Serialize)]
+struct Message<'a> {
+    role: &'a str,
+    content: &'a str,
+}
+
+#[derive(Deserialize)]
+struct MessagesResponse {
+    #[serde(default)]
+    model: Option<String>,
+    #[serde(default)]
+    content: Vec<ContentBlock>,
+    #[serde(default)]
+    stop_reason: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ContentBlock {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    text: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ModelsResponse {
+    #[serde(default)]
+    data: Vec<ModelEntry>,
+}
+
+#[derive(Deserialize)]
+struct ModelEntry {
+    id: String,
+}
+
+pub struct AnthropicProvider {
+    pub(crate) http: Arc<HttpPool>,
+}
+
+#[async_trait]
+impl TranslationProvider for AnthropicProvider {
+    fn id(&self) -> &'static str {
+        "anthropic"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Anthropic"
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            requires_api_key: true,
+            accepts_access_token: false,
+            requires_model: false,
+            enabled_by_default: true,
+            supports_model_selection: true,
+            supports_auto_detect: true,
+            is_llm: true,
+        }
+    }
+
+    fn default_base_url(&self) -> &'static str {
+        DEFAULT_BASE_URL
+    }
+
+    async fn translate(&self, request: &TranslationRequest) -> Result<TranslationOutcome, String> {
+        let api_key = request
+            .settings
+            .api_key()
+            .ok_or_else(|| "Anthropic APIキーが設定されていません".to_string())?;
+        let options: AnthropicOptions = request.settings.options();
+
+        // Use provided model or pick the newest fast model
+        let model_name = match request.settings.model() {
+            Some(m) => m.to_string(),
+            None => {
+                let m = latest_fast_model(&self.list_models(&request.settings).await?)
+                    .ok_or_else(|| "No Anthropic models found".to_string())?;
+                println!("Auto-selected Anthropic model: {}", m);
+                m
+            }
+        };
+
+        let client = self.http.client(15);
+        let url = format!("{}/v1/messages", self.base_url(&request.settings));
+
+        let system_instruction = prompt::system_instruction(language_name(&request.target_lang));
+        let body = MessagesRequest {
+            model: &model_name,
+            max_tokens: options.max_tokens.unwrap_or(DEFAULT_MAX_TOKENS),
+            system: &system_instruction,
+            messages: vec![Message { role: "user", content: &request.text }],
+            temperature: 0.0,
+        };
+
+        println!("Sending Anthropic request (model: {})", model_name);
+
+        let (response, attempts) = send_with_retry(
+            SERVICE,
+            client
+                .post(&url)
+                .header("x-api-key", api_key)
+                .header("anthropic-version", API_VERSION)
+                .json(&body),
+        )
+        .await
+        .map_err(|e| map_request_error(SERVICE, e))?;
+
+        println!("Response status: {}", response.status());
+        if !response.status().is_success() {
+            return Err(status_error("Anthropic", response).await);
+        }
+
+        let body = response
+            .text()
+            .await
+            .map_err(|e| format!("Failed to read response body: {}", e))?;
+
+        if is_verbose() {
+            println!("Response body (first 300 chars): {}", truncate_chars(&body, 300));
+        }
+
+        let parsed = serde_json::from_str::<MessagesResponse>(&body)
+            .map_err(|e| format!("Failed to parse Anthropic response: {}", e))?;
+
+        if parsed.stop_reason.as_deref() == Some("max_tokens") {
+            println!("Warning: Anthropic response was truncated at max_tokens");
+        }
+
+        let translated: String = parsed
+            .content
+            .into_iter()
+            .filter(|block| block.kind == "text")
+            .filter_map(|block| block.text)
+            .collect();
+        if translated.trim().is_empty() {
+            return Err("Translation not found in Anthropic response".to_string());
+        }
+
+        Ok(self
+            .outcome(translated.trim().to_string(), Some(parsed.model.unwrap_or(model_name)))
+            .with_attempts(attempts))
+    }
+
+    async fn list_models(&self, settings: &ProviderSettings) -> Result<Vec<String>, String> {
+        let api_key = settings
+            .api_key()
+            .ok_or_else(|| "Anthropic APIキーが設定されていません".to_string())?;
+        let client = self.http.client(10);
+
+        let (response, _) = send_with_retry(
+            SERVICE,
+            client
+                .get(format!("{}/v1/models", self.base_url(settings)))
+                .query(&[("limit", "100")])
+                .header("x-api-key", api_key)
+                .header("anthropic-version", API_VERSION),
+        )
+        .await
+        .map_err(|e| map_request_error(SERVICE, e))?;
+
+        if !response.status().is_success() {
+            return Err(status_error("Anthropic", response).await);
+        }
+
+        let models = response
+            .json::<ModelsResponse>()
+            .await
+            .map_err(|e| format!("Failed to parse Anthropic model list: {}", e))?;
+
+        // The API lists the most recently released models first
+        Ok(models.data.into_iter().map(|m| m.id).collect())
+    }
+}
+
+// Prefer the newest Haiku model for latency, otherwise the newest model overall
+fn latest_fast_model(models: &[String]) -> Option<String> {
+    models
+        .iter()
+        .find(|m| m.contains("haiku"))
+        .or_else(|| models.first())
+        .cloned()
+}
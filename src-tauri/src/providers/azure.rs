@@ -0,0 +1,203 @@
+// Microsoft Translator (Azure AI Translator, API v3.0)
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use super::segment::split_for_translation;
+use super::{
+    map_request_error, send_with_retry, status_error, DetectedLanguage, HttpPool,
+    ProviderCapabilities, TranslationOutcome, TranslationProvider, TranslationRequest,
+};
+use crate::is_verbose;
+
+const SERVICE: &str = "Azure Translator";
+const DEFAULT_BASE_URL: &str = "https://api.cognitive.microsofttranslator.com";
+// Request limits of the v3 translate API: elements per request and characters per request
+const MAX_BATCH_ITEMS: usize = 1000;
+const MAX_BATCH_CHARS: usize = 50_000;
+// Lines are packed into elements of up to this size, so wrapped paragraphs keep their context
+const MAX_ITEM_CHARS: usize = 5_000;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct AzureOptions {
+    // Resource region (e.g. "japaneast"); not needed for global resources
+    region: Option<String>,
+}
+
+#[derive(Serialize)]
+struct TextItem<'a> {
+    #[serde(rename = "Text")]
+    text: &'a str,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TranslateResult {
+    #[serde(default)]
+    detected_language: Option<AzureDetectedLanguage>,
+    translations: Vec<AzureTranslation>,
+}
+
+#[derive(Deserialize)]
+struct AzureDetectedLanguage {
+    language: String,
+    score: f64,
+}
+
+#[derive(Deserialize)]
+struct AzureTranslation {
+    text: String,
+}
+
+pub struct AzureTranslatorProvider {
+    pub(crate) http: Arc<HttpPool>,
+}
+
+#[async_trait]
+impl TranslationProvider for AzureTranslatorProvider {
+    fn id(&self) -> &'static str {
+        "azure"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Microsoft Translator"
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            requires_api_key: true,
+            accepts_access_token: false,
+            requires_model: false,
+            enabled_by_default: true,
+            supports_model_selection: false,
+            supports_auto_detect: true,
+            is_llm: false,
+        }
+    }
+
+    fn default_base_url(&self) -> &'static str {
+        DEFAULT_BASE_URL
+    }
+
+    async fn translate(&self, request: &TranslationRequest) -> Result<TranslationOutcome, String> {
+        let api_key = request
+            .settings
+            .api_key()
+            .ok_or_else(|| "Azure Translatorのサブスクリプションキーが設定されていません".to_string())?;
+        let options: AzureOptions = request.settings.options();
+
+        let client = self.http.client(10);
+        let url = format!("{}/translate", self.base_url(&request.settings));
+
+        // Whole lines are packed into elements (split at sentences only when a line is too long)
+        let segmented = split_for_translation(&request.text, MAX_ITEM_CHARS);
+        if segmented.chunks.is_empty() {
+            return Err("翻訳するテキストがありません".to_string());
+        }
+
+        let mut query: Vec<(&str, &str)> = vec![
+            ("api-version", "3.0"),
+            ("to", azure_lang_code(&request.target_lang)),
+        ];
+        if request.source_lang != "auto" {
+            query.push(("from", azure_lang_code(&request.source_lang)));
+        }
+        let region = options.region.as_deref().map(str::trim).filter(|r| !r.is_empty());
+
+        let mut translations: Vec<String> = Vec::with_capacity(segmented.chunks.len());
+        let mut detected: Vec<AzureDetectedLanguage> = Vec::new();
+        let mut attempts = 1;
+        let lengths: Vec<usize> = segmented.chunks.iter().map(|chunk| chunk.text.chars().count()).collect();
+        for batch in batches(&lengths) {
+            let items: Vec<TextItem> = segmented.chunks[batch]
+                .iter()
+                .map(|chunk| TextItem { text: &chunk.text })
+                .collect();
+            println!("Sending batch of {} items to Azure Translator...", items.len());
+
+            let mut builder = client
+                .post(&url)
+                .query(&query)
+                .header("Ocp-Apim-Subscription-Key", api_key)
+                .json(&items);
+            if let Some(region) = region {
+                builder = builder.header("Ocp-Apim-Subscription-Region", region);
+            }
+
+            let (response, batch_attempts) = send_with_retry(SERVICE, builder)
+                .await
+                .map_err(|e| map_request_error(SERVICE, e))?;
+            attempts = attempts.max(batch_attempts);
+
+            println!("Response status: {}", response.status());
+            if !response.status().is_success() {
+                return Err(status_error(SERVICE, response).await);
+            }
+
+            let results = response
+                .json::<Vec<TranslateResult>>()
+                .await
+                .map_err(|e| format!("Failed to parse Azure Translator response: {}", e))?;
+            if results.len() != items.len() {
+                return Err(format!(
+                    "Azure Translator returned {} results for {} items",
+                    results.len(),
+                    items.len()
+                ));
+            }
+            for result in results {
+                detected.extend(result.detected_language);
+                translations.push(result.translations.into_iter().next().map(|t| t.text).unwrap_or_default());
+            }
+        }
+
+        // The most confident per-item detection represents the whole selection
+        let detected_language = detected
+            .into_iter()
+            .max_by(|a, b| a.score.total_cmp(&b.score))
+            .map(|d| DetectedLanguage { language: d.language, confidence: d.score });
+
+        // Put translations back into the original layout (blank lines and indentation were not sent)
+        let translated = segmented.reassemble(&translations);
+
+        if translated.trim().is_empty() {
+            return Err("Translation not found in Azure Translator response".to_string());
+        }
+        if is_verbose() {
+            println!("Final translation: {}", translated);
+        }
+
+        let mut outcome = self.outcome(translated, None).with_attempts(attempts);
+        outcome.detected_language = detected_language;
+        Ok(outcome)
+    }
+}
+
+// Consecutive index ranges of elements that fit in one request (MAX_BATCH_ITEMS, MAX_BATCH_CHARS)
+fn batches(item_lengths: &[usize]) -> Vec<std::ops::Range<usize>> {
+    let mut ranges = Vec::new();
+    let (mut start, mut chars) = (0, 0);
+    for (index, len) in item_lengths.iter().enumerate() {
+        if index > start && (index - start >= MAX_BATCH_ITEMS || chars + len > MAX_BATCH_CHARS) {
+            ranges.push(start..index);
+            start = index;
+            chars = 0;
+        }
+        chars += len;
+    }
+    if start < item_lengths.len() {
+        ranges.push(start..item_lengths.len());
+    }
+    ranges
+}
+
+// Azure uses script-qualified codes for Chinese
+fn azure_lang_code(code: &str) -> &str {
+    match code {
+        "zh" => "zh-Hans",
+        other => other,
+    }
+}
@@ -0,0 +1,546 @@
+// Translation cache: re-selecting the same text is answered without contacting the provider
+// (instant, and no API quota used). Entries live in a small in-memory LRU for the session; when
+// the user opts in, they are also kept as one JSON file per entry under
+// `<app data>/cache/translations/`, with a TTL and a cap on disk usage.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use super::prompt::PROMPT_VERSION;
+use super::{TranslationOutcome, TranslationRequest};
+use crate::truncate_chars;
+
+const MEMORY_CAPACITY: usize = 256;
+const TTL: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+const MAX_DISK_BYTES: u64 = 20 * 1024 * 1024;
+// The disk directory is pruned (expired entries, size cap) every this many writes
+const PRUNE_EVERY: u64 = 50;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CacheKey {
+    pub provider_id: String,
+    pub model: String,
+    pub source_lang: String,
+    pub target_lang: String,
+    pub prompt_version: u32,
+    // Endpoint override and provider options (formality, glossary, ...) also change the output
+    pub variant: String,
+    pub text: String,
+}
+
+impl CacheKey {
+    pub fn new(provider_id: &str, request: &TranslationRequest) -> Self {
+        let settings = &request.settings;
+        Self {
+            provider_id: provider_id.to_string(),
+            model: settings.model().unwrap_or("default").to_string(),
+            source_lang: request.source_lang.clone(),
+            target_lang: request.target_lang.clone(),
+            prompt_version: PROMPT_VERSION,
+            variant: format!(
+                "{}|{}",
+                settings.base_url().unwrap_or_default(),
+                serde_json::Value::Object(settings.options.clone())
+            ),
+            // Exact text: the translation follows its line breaks and indentation
+            text: request.text.clone(),
+        }
+    }
+
+    // File name on disk; the full key is stored in the file and compared on read. The hash must
+    // stay the same across Rust releases (std's DefaultHasher may change), hence FNV-1a.
+    fn file_name(&self) -> String {
+        let serialized = serde_json::to_vec(self).unwrap_or_default();
+        format!("{:016x}.json", fnv1a_64(&serialized))
+    }
+}
+
+fn fnv1a_64(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+    bytes.iter().fold(OFFSET_BASIS, |hash, byte| (hash ^ u64::from(*byte)).wrapping_mul(PRIME))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    key: CacheKey,
+    outcome: TranslationOutcome,
+    // Unix seconds
+    created_at: u64,
+}
+
+impl CacheEntry {
+    fn is_expired(&self) -> bool {
+        unix_now().saturating_sub(self.created_at) > TTL.as_secs()
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CacheStats {
+    pub memory_entries: usize,
+    pub disk_entries: usize,
+    pub disk_bytes: u64,
+    // Since app start
+    pub hits: u64,
+    pub misses: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CacheEntryInfo {
+    pub provider_id: String,
+    pub model: String,
+    pub source_lang: String,
+    pub target_lang: String,
+    pub text_preview: String,
+    pub translation_preview: String,
+    pub created_at: u64,
+}
+
+#[derive(Default)]
+struct MemoryLru {
+    // Entry and the tick of its last use
+    entries: HashMap<CacheKey, (CacheEntry, u64)>,
+    tick: u64,
+}
+
+impl MemoryLru {
+    fn get(&mut self, key: &CacheKey) -> Option<CacheEntry> {
+        self.tick += 1;
+        let tick = self.tick;
+        let (entry, last_used) = self.entries.get_mut(key)?;
+        *last_used = tick;
+        Some(entry.clone())
+    }
+
+    fn insert(&mut self, entry: CacheEntry) {
+        self.tick += 1;
+        self.entries.insert(entry.key.clone(), (entry, self.tick));
+        if self.entries.len() > MEMORY_CAPACITY {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                self.entries.remove(&oldest);
+            }
+        }
+    }
+}
+
+pub struct TranslationCache {
+    // None keeps the cache in memory only (app data dir unavailable)
+    dir: Option<PathBuf>,
+    // Off by default: selected text is written to disk only when the user enables it
+    persist: AtomicBool,
+    memory: Mutex<MemoryLru>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    writes: AtomicU64,
+}
+
+impl TranslationCache {
+    pub fn new(dir: Option<PathBuf>) -> Self {
+        let cache = Self {
+            dir,
+            persist: AtomicBool::new(false),
+            memory: Mutex::new(MemoryLru::default()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            writes: AtomicU64::new(0),
+        };
+        cache.prune_disk();
+        cache
+    }
+
+    // Enable or disable the disk tier; disabling removes what was already written
+    pub fn set_persist(&self, persist: bool) -> Result<(), String> {
+        let was_persisting = self.persist.swap(persist, Ordering::Relaxed);
+        if was_persisting != persist {
+            println!("Translation cache on disk {}", if persist { "enabled" } else { "disabled" });
+        }
+        if !persist {
+            self.clear_disk()?;
+        }
+        Ok(())
+    }
+
+    // Directory of the disk tier, when it is enabled
+    fn disk_dir(&self) -> Option<&Path> {
+        self.dir.as_deref().filter(|_| self.persist.load(Ordering::Relaxed))
+    }
+
+    // Cached outcome for `key`, marked `cached`
+    pub fn get(&self, key: &CacheKey) -> Option<TranslationOutcome> {
+        let entry = self.get_entry(key);
+        let counter = if entry.is_some() { &self.hits } else { &self.misses };
+        counter.fetch_add(1, Ordering::Relaxed);
+
+        entry.map(|entry| {
+            println!("Translation cache hit ({}, {} chars)", key.provider_id, key.text.chars().count());
+            TranslationOutcome { cached: true, ..entry.outcome }
+        })
+    }
+
+    fn get_entry(&self, key: &CacheKey) -> Option<CacheEntry> {
+        if let Ok(mut memory) = self.memory.lock() {
+            match memory.get(key) {
+                Some(entry) if !entry.is_expired() => return Some(entry),
+                Some(_) => {
+                    memory.entries.remove(key);
+                }
+                None => {}
+            }
+        }
+
+        let path = self.disk_dir()?.join(key.file_name());
+        let entry: CacheEntry = serde_json::from_slice(&fs::read(&path).ok()?).ok()?;
+        // A hash collision or stale file is simply a miss
+        if entry.key != *key {
+            return None;
+        }
+        if entry.is_expired() {
+            let _ = fs::remove_file(&path);
+            return None;
+        }
+        if let Ok(mut memory) = self.memory.lock() {
+            memory.insert(entry.clone());
+        }
+        Some(entry)
+    }
+
+    // Store a successful translation (retry and fallback details are not cached)
+    pub fn put(&self, key: CacheKey, outcome: &TranslationOutcome) {
+        let entry = CacheEntry {
+            key,
+            outcome: TranslationOutcome {
+                attempts: 1,
+                fallback_errors: Vec::new(),
+                cached: false,
+                ..outcome.clone()
+            },
+            created_at: unix_now(),
+        };
+
+        if let Some(dir) = self.disk_dir() {
+            let result = fs::create_dir_all(dir)
+                .and_then(|_| serde_json::to_vec(&entry).map_err(std::io::Error::other))
+                .and_then(|json| fs::write(dir.join(entry.key.file_name()), json));
+            if let Err(e) = result {
+                eprintln!("Failed to write translation cache entry: {}", e);
+            }
+            if self.writes.fetch_add(1, Ordering::Relaxed) % PRUNE_EVERY == PRUNE_EVERY - 1 {
+                self.prune_disk();
+            }
+        }
+
+        if let Ok(mut memory) = self.memory.lock() {
+            memory.insert(entry);
+        }
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        let files = self.disk_dir().map(disk_files).unwrap_or_default();
+        CacheStats {
+            memory_entries: self.memory.lock().map(|m| m.entries.len()).unwrap_or(0),
+            disk_entries: files.len(),
+            disk_bytes: files.iter().map(|f| f.len).sum(),
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+
+    // Most recent entries first
+    pub fn entries(&self, limit: usize) -> Vec<CacheEntryInfo> {
+        let mut entries: Vec<CacheEntry> = match self.disk_dir() {
+            Some(dir) => disk_files(dir)
+                .iter()
+                .filter_map(|f| serde_json::from_slice(&fs::read(&f.path).ok()?).ok())
+                .collect(),
+            None => self
+                .memory
+                .lock()
+                .map(|m| m.entries.values().map(|(entry, _)| entry.clone()).collect())
+                .unwrap_or_default(),
+        };
+        entries.retain(|entry| !entry.is_expired());
+        entries.sort_by_key(|entry| std::cmp::Reverse(entry.created_at));
+        entries
+            .into_iter()
+            .take(limit)
+            .map(|entry| CacheEntryInfo {
+                provider_id: entry.key.provider_id,
+                model: entry.key.model,
+                source_lang: entry.key.source_lang,
+                target_lang: entry.key.target_lang,
+                text_preview: truncate_chars(&entry.key.text, 80),
+                translation_preview: truncate_chars(&entry.outcome.translated_text, 80),
+                created_at: entry.created_at,
+            })
+            .collect()
+    }
+
+    // Remove every entry; returns how many were on disk (or in memory when memory-only)
+    pub fn clear(&self) -> Result<usize, String> {
+        let in_memory = match self.memory.lock() {
+            Ok(mut memory) => std::mem::take(&mut memory.entries).len(),
+            Err(_) => 0,
+        };
+        let on_disk = self.clear_disk()?;
+        Ok(if self.disk_dir().is_some() { on_disk } else { in_memory })
+    }
+
+    // Delete every entry file (also those written before the disk tier was turned off)
+    fn clear_disk(&self) -> Result<usize, String> {
+        let Some(dir) = self.dir.as_deref() else {
+            return Ok(0);
+        };
+        let files = disk_files(dir);
+        for file in &files {
+            fs::remove_file(&file.path)
+                .map_err(|e| format!("キャッシュを削除できません ({}): {}", file.path.display(), e))?;
+        }
+        if !files.is_empty() {
+            println!("Translation cache cleared on disk ({} entries)", files.len());
+        }
+        Ok(files.len())
+    }
+
+    fn prune_disk(&self) {
+        let Some(dir) = self.dir.as_deref() else { return };
+        let removed = prune_dir(dir, SystemTime::now(), TTL, MAX_DISK_BYTES);
+        if removed > 0 {
+            println!("Translation cache pruned ({} entries removed)", removed);
+        }
+    }
+}
+
+// Drop files older than `ttl`, then the oldest ones until the directory fits in `max_bytes`;
+// returns how many were removed
+fn prune_dir(dir: &Path, now: SystemTime, ttl: Duration, max_bytes: u64) -> usize {
+    let (expired, mut files): (Vec<_>, Vec<_>) = disk_files(dir)
+        .into_iter()
+        .partition(|f| now.duration_since(f.modified).map(|age| age > ttl).unwrap_or(false));
+    for file in &expired {
+        let _ = fs::remove_file(&file.path);
+    }
+
+    let mut total: u64 = files.iter().map(|f| f.len).sum();
+    files.sort_by_key(|f| f.modified);
+    let mut removed = expired.len();
+    for file in files {
+        if total <= max_bytes {
+            break;
+        }
+        if fs::remove_file(&file.path).is_ok() {
+            total = total.saturating_sub(file.len);
+            removed += 1;
+        }
+    }
+    removed
+}
+
+struct DiskFile {
+    path: PathBuf,
+    len: u64,
+    modified: SystemTime,
+}
+
+fn disk_files(dir: &Path) -> Vec<DiskFile> {
+    let Ok(read_dir) = fs::read_dir(dir) else { return Vec::new() };
+    read_dir
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                return None;
+            }
+            let metadata = entry.metadata().ok()?;
+            Some(DiskFile {
+                path,
+                len: metadata.len(),
+                modified: metadata.modified().unwrap_or(UNIX_EPOCH),
+            })
+        })
+        .collect()
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::ProviderSettings;
+
+    // Fresh directory under the system temp dir, removed when dropped
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let path = std::env::temp_dir()
+                .join(format!("afterpot-cache-test-{}-{}", std::process::id(), name));
+            let _ = fs::remove_dir_all(&path);
+            fs::create_dir_all(&path).unwrap();
+            Self(path)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn request(text: &str) -> TranslationRequest {
+        TranslationRequest {
+            text: text.to_string(),
+            source_lang: "en".to_string(),
+            target_lang: "ja".to_string(),
+            settings: ProviderSettings::default(),
+            chunk_sink: None,
+            limiter: None,
+        }
+    }
+
+    fn key(text: &str) -> CacheKey {
+        CacheKey::new("deepl", &request(text))
+    }
+
+    fn outcome(translated: &str) -> TranslationOutcome {
+        TranslationOutcome {
+            provider_id: "deepl".to_string(),
+            provider_name: "DeepL".to_string(),
+            translated_text: translated.to_string(),
+            model_used: None,
+            detected_language: None,
+            attempts: 2,
+            fallback_errors: Vec::new(),
+            cached: false,
+            dictionary: None,
+            segments: Vec::new(),
+            latency_ms: 0,
+        }
+    }
+
+    fn entry(text: &str, created_at: u64) -> CacheEntry {
+        CacheEntry { key: key(text), outcome: outcome(text), created_at }
+    }
+
+    fn persistent_cache(dir: &TempDir) -> TranslationCache {
+        let cache = TranslationCache::new(Some(dir.0.clone()));
+        cache.set_persist(true).unwrap();
+        cache
+    }
+
+    #[test]
+    fn key_keeps_the_exact_text() {
+        let text = "  first line\n    indented  line\r\n";
+        assert_eq!(key(text).text, text);
+        assert_ne!(key("a  b"), key("a b"));
+        assert_ne!(key("a\nb"), key("a b"));
+    }
+
+    #[test]
+    fn file_name_is_a_stable_fnv1a_hash() {
+        // Reference values of 64-bit FNV-1a
+        assert_eq!(fnv1a_64(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a_64(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(fnv1a_64(b"foobar"), 0x8594_4171_f739_67e8);
+
+        let name = key("hello").file_name();
+        assert_eq!(name.len(), "0123456789abcdef.json".len());
+        assert!(name.ends_with(".json"));
+        assert_eq!(name, key("hello").file_name());
+        assert_ne!(name, key("hello!").file_name());
+    }
+
+    #[test]
+    fn memory_evicts_the_least_recently_used_entry() {
+        let mut lru = MemoryLru::default();
+        for i in 0..MEMORY_CAPACITY {
+            lru.insert(entry(&format!("text {}", i), unix_now()));
+        }
+        // Touch the oldest entry so that the second one becomes the least recently used
+        assert!(lru.get(&key("text 0")).is_some());
+        lru.insert(entry("one more", unix_now()));
+
+        assert_eq!(lru.entries.len(), MEMORY_CAPACITY);
+        assert!(lru.get(&key("text 0")).is_some());
+        assert!(lru.get(&key("text 1")).is_none());
+        assert!(lru.get(&key("one more")).is_some());
+    }
+
+    #[test]
+    fn expired_entries_are_misses() {
+        let cache = TranslationCache::new(None);
+        if let Ok(mut memory) = cache.memory.lock() {
+            memory.insert(entry("old", 0));
+        }
+        assert!(cache.get(&key("old")).is_none());
+        assert_eq!(cache.stats().memory_entries, 0);
+
+        let dir = TempDir::new("ttl");
+        let cache = persistent_cache(&dir);
+        let path = dir.0.join(key("old").file_name());
+        fs::write(&path, serde_json::to_vec(&entry("old", 0)).unwrap()).unwrap();
+        assert!(cache.get(&key("old")).is_none());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn disk_tier_survives_a_restart() {
+        let dir = TempDir::new("disk");
+        persistent_cache(&dir).put(key("hello"), &outcome("こんにちは"));
+
+        let cache = persistent_cache(&dir);
+        let hit = cache.get(&key("hello")).unwrap();
+        assert_eq!(hit.translated_text, "こんにちは");
+        assert!(hit.cached);
+        assert_eq!(hit.attempts, 1);
+        assert_eq!(cache.stats().disk_entries, 1);
+
+        // Turning the disk tier off removes what was written
+        cache.set_persist(false).unwrap();
+        assert!(disk_files(&dir.0).is_empty());
+    }
+
+    #[test]
+    fn prune_drops_expired_then_oldest_files() {
+        let dir = TempDir::new("prune");
+        let now = SystemTime::now();
+        let ttl = Duration::from_secs(3600);
+        let write = |name: &str, age: Duration| {
+            let path = dir.0.join(name);
+            fs::write(&path, [b'x'; 100]).unwrap();
+            fs::File::options().write(true).open(&path).unwrap().set_modified(now - age).unwrap();
+        };
+        write("expired.json", ttl * 2);
+        write("oldest.json", Duration::from_secs(300));
+        write("older.json", Duration::from_secs(200));
+        write("newest.json", Duration::from_secs(100));
+        fs::write(dir.0.join("notes.txt"), "not an entry").unwrap();
+
+        assert_eq!(prune_dir(&dir.0, now, ttl, 200), 2);
+        let mut left: Vec<String> = disk_files(&dir.0)
+            .into_iter()
+            .filter_map(|f| Some(f.path.file_name()?.to_str()?.to_string()))
+            .collect();
+        left.sort();
+        assert_eq!(left, ["newest.json", "older.json"]);
+        assert!(dir.0.join("notes.txt").exists());
+
+        // Within limits: nothing to do
+        assert_eq!(prune_dir(&dir.0, now, ttl, 200), 0);
+    }
+}
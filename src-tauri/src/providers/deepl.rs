@@ -0,0 +1,162 @@
+// DeepL API (Free and Pro plans, requires auth key)
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    map_request_error, resolve_base_url, send_with_retry, status_error, HttpPool,
+    ProviderCapabilities, ProviderSettings, TranslationOutcome, TranslationProvider,
+    TranslationRequest,
+};
+use crate::is_verbose;
+
+const SERVICE: &str = "DeepL";
+const FREE_BASE_URL: &str = "https://api-free.deepl.com";
+const PRO_BASE_URL: &str = "https://api.deepl.com";
+
+// Provider-specific options (ProviderSettings.options)
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct DeeplOptions {
+    // "free" | "pro"; when unset the plan is inferred from the key (Free keys end with ":fx")
+    plan: Option<String>,
+    // "default" | "more" | "less" | "prefer_more" | "prefer_less"
+    formality: Option<String>,
+    // "0" | "1" | "nonewlines"
+    split_sentences: Option<String>,
+    preserve_formatting: Option<bool>,
+}
+
+#[derive(Serialize)]
+struct DeeplRequest<'a> {
+    text: [&'a str; 1],
+    target_lang: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source_lang: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    formality: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    split_sentences: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    preserve_formatting: Option<bool>,
+}
+
+#[derive(Deserialize)]
+struct DeeplResponse {
+    translations: Vec<DeeplTranslation>,
+}
+
+#[derive(Deserialize)]
+struct DeeplTranslation {
+    text: String,
+}
+
+pub struct DeeplProvider {
+    pub(crate) http: Arc<HttpPool>,
+}
+
+impl DeeplProvider {
+    fn is_free_plan(settings: &ProviderSettings, options: &DeeplOptions) -> bool {
+        match options.plan.as_deref() {
+            Some("pro") => false,
+            Some("free") => true,
+            _ => settings.api_key().is_none_or(|k| k.ends_with(":fx")),
+        }
+    }
+}
+
+#[async_trait]
+impl TranslationProvider for DeeplProvider {
+    fn id(&self) -> &'static str {
+        "deepl"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "DeepL"
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            requires_api_key: true,
+            accepts_access_token: false,
+            requires_model: false,
+            enabled_by_default: true,
+            supports_model_selection: false,
+            supports_auto_detect: true,
+            is_llm: false,
+        }
+    }
+
+    fn default_base_url(&self) -> &'static str {
+        FREE_BASE_URL
+    }
+
+    // Free and Pro keys are served by different hosts
+    fn base_url(&self, settings: &ProviderSettings) -> String {
+        let options: DeeplOptions = settings.options();
+        let default = if Self::is_free_plan(settings, &options) { FREE_BASE_URL } else { PRO_BASE_URL };
+        resolve_base_url(self.id(), settings.base_url(), default)
+    }
+
+    async fn translate(&self, request: &TranslationRequest) -> Result<TranslationOutcome, String> {
+        let api_key = request
+            .settings
+            .api_key()
+            .ok_or_else(|| "DeepL認証キーが設定されていません".to_string())?;
+        let options: DeeplOptions = request.settings.options();
+
+        let client = self.http.client(10);
+        let url = format!("{}/v2/translate", self.base_url(&request.settings));
+
+        let body = DeeplRequest {
+            text: [request.text.as_str()],
+            target_lang: target_lang_code(&request.target_lang),
+            source_lang: (request.source_lang != "auto").then(|| request.source_lang.to_uppercase()),
+            formality: options.formality.as_deref().filter(|f| *f != "default"),
+            split_sentences: options.split_sentences.as_deref(),
+            preserve_formatting: options.preserve_formatting,
+        };
+
+        println!("Sending request to DeepL ({})...", url);
+
+        let (response, attempts) = send_with_retry(
+            SERVICE,
+            client
+                .post(&url)
+                .header("Authorization", format!("DeepL-Auth-Key {}", api_key))
+                .json(&body),
+        )
+        .await
+        .map_err(|e| map_request_error(SERVICE, e))?;
+
+        println!("Response status: {}", response.status());
+        if !response.status().is_success() {
+            return Err(status_error(SERVICE, response).await);
+        }
+
+        let parsed = response
+            .json::<DeeplResponse>()
+            .await
+            .map_err(|e| format!("Failed to parse DeepL response: {}", e))?;
+
+        let result: String = parsed.translations.into_iter().map(|t| t.text).collect();
+        if result.is_empty() {
+            return Err("Translation not found in DeepL response".to_string());
+        }
+        if is_verbose() {
+            println!("Final translation: {}", result);
+        }
+        Ok(self.outcome(result, None).with_attempts(attempts))
+    }
+}
+
+// DeepL expects upper-case codes and a regional variant for English/Portuguese targets
+fn target_lang_code(code: &str) -> String {
+    match code {
+        "en" => "EN-US".to_string(),
+        "pt" => "PT-PT".to_string(),
+        other => other.to_uppercase(),
+    }
+}
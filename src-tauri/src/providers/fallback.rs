@@ -0,0 +1,61 @@
+// Provider fallback chains: try providers in order until one answers
+// (e.g. Gemini → DeepL → Google web for ja→en)
+
+use std::sync::Arc;
+
+use super::cache::{CacheKey, TranslationCache};
+use super::mixed::translate_mixed;
+use super::{ProviderFailure, TranslationOutcome, TranslationProvider, TranslationRequest};
+
+// Translate with each (provider, request) in turn; the outcome is tagged with the provider
+// that actually answered and lists the providers that failed before it.
+// A provider over its local rate limit counts as failed, so the chain moves on without waiting.
+// A cached answer from a provider counts as that provider answering.
+pub async fn translate_with_fallback(
+    chain: Vec<(Arc<dyn TranslationProvider>, TranslationRequest)>,
+    cache: Arc<TranslationCache>,
+) -> Result<TranslationOutcome, String> {
+    if chain.is_empty() {
+        return Err("フォールバックチェーンにプロバイダーがありません".to_string());
+    }
+
+    let mut failures: Vec<ProviderFailure> = Vec::new();
+    for (provider, request) in chain {
+        let key = CacheKey::new(provider.id(), &request);
+        let result = match cache.get(&key) {
+            Some(outcome) => Ok(outcome),
+            None => translate_mixed(provider.as_ref(), &request)
+                .await
+                .inspect(|outcome| cache.put(key, outcome))
+                .map_err(String::from),
+        };
+        match result {
+            Ok(mut outcome) => {
+                if !failures.is_empty() {
+                    println!(
+                        "Fallback: {} answered after {} failed provider(s)",
+                        provider.id(),
+                        failures.len()
+                    );
+                }
+                outcome.fallback_errors = failures;
+                return Ok(outcome);
+            }
+            Err(error) => {
+                println!("Fallback: {} failed, trying next provider: {}", provider.id(), error);
+                failures.push(ProviderFailure {
+                    provider_id: provider.id().to_string(),
+                    provider_name: provider.display_name().to_string(),
+                    error,
+                });
+            }
+        }
+    }
+
+    let summary = failures
+        .iter()
+        .map(|f| format!("{}: {}", f.provider_name, f.error))
+        .collect::<Vec<_>>()
+        .join(" / ");
+    Err(format!("すべてのプロバイダーで翻訳に失敗しました（{}）", summary))
+}
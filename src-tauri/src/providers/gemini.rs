@@ -0,0 +1,480 @@
+// Google Gemini Generative Language API (requires API key)
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    language_name, map_request_error, prompt, send_with_retry, status_error, HttpPool,
+    ProviderCapabilities, ProviderSettings, TranslationOutcome, TranslationProvider,
+    TranslationRequest,
+};
+use crate::{is_verbose, truncate_chars};
+
+const SERVICE: &str = "Gemini API";
+pub const DEFAULT_BASE_URL: &str = "https://generativelanguage.googleapis.com";
+const API_VERSION: &str = "v1beta";
+// Longest wait for the next streamed chunk before giving up
+const CHUNK_TIMEOUT: Duration = Duration::from_secs(15);
+// Longest wait for the response headers, including retries of the initial request
+const FIRST_RESPONSE_TIMEOUT: Duration = Duration::from_secs(45);
+// Deterministic output unless a model's settings say otherwise
+const DEFAULT_TEMPERATURE: f32 = 0.0;
+// Key in `models` whose settings apply to models without their own entry
+const ANY_MODEL: &str = "*";
+// Categories covered by the `safety_threshold` setting
+const SAFETY_CATEGORIES: [&str; 4] = [
+    "HARM_CATEGORY_HARASSMENT",
+    "HARM_CATEGORY_HATE_SPEECH",
+    "HARM_CATEGORY_SEXUALLY_EXPLICIT",
+    "HARM_CATEGORY_DANGEROUS_CONTENT",
+];
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct GeminiOptions {
+    // Generation settings per model name (e.g. "gemini-2.5-flash"), or "*" for every other model
+    models: HashMap<String, GenerationSettings>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+struct GenerationSettings {
+    temperature: Option<f32>,
+    top_p: Option<f32>,
+    max_output_tokens: Option<u32>,
+    // Thinking models only: 0 disables thinking, -1 lets the model decide
+    thinking_budget: Option<i32>,
+    // e.g. "BLOCK_NONE", "BLOCK_ONLY_HIGH"; None keeps the API defaults
+    safety_threshold: Option<String>,
+}
+
+impl GeminiOptions {
+    // Settings for `model`: its own entry, with unset fields taken from the "*" entry
+    fn settings_for(&self, model: &str) -> GenerationSettings {
+        let any = self.models.get(ANY_MODEL).cloned().unwrap_or_default();
+        let Some(own) = self.models.get(model) else { return any };
+        GenerationSettings {
+            temperature: own.temperature.or(any.temperature),
+            top_p: own.top_p.or(any.top_p),
+            max_output_tokens: own.max_output_tokens.or(any.max_output_tokens),
+            thinking_budget: own.thinking_budget.or(any.thinking_budget),
+            safety_threshold: own.safety_threshold.clone().or(any.safety_threshold),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GenerateContentRequest<'a> {
+    // Kept apart from the text so instructions inside the text are not followed
+    system_instruction: Content<'a>,
+    contents: Vec<Content<'a>>,
+    generation_config: GenerationConfig,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    safety_settings: Vec<SafetySetting<'a>>,
+}
+
+#[derive(Serialize)]
+struct Content<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    role: Option<&'a str>,
+    parts: Vec<Part<'a>>,
+}
+
+#[derive(Serialize)]
+struct Part<'a> {
+    text: &'a str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GenerationConfig {
+    temperature: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_output_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thinking_config: Option<ThinkingConfig>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ThinkingConfig {
+    thinking_budget: i32,
+}
+
+#[derive(Serialize)]
+struct SafetySetting<'a> {
+    category: &'a str,
+    threshold: &'a str,
+}
+
+impl<'a> GenerateContentRequest<'a> {
+    fn new(system_instruction: &'a str, text: &'a str, settings: &'a GenerationSettings) -> Self {
+        Self {
+            system_instruction: Content { role: None, parts: vec![Part { text: system_instruction }] },
+            contents: vec![Content { role: Some("user"), parts: vec![Part { text }] }],
+            generation_config: GenerationConfig {
+                temperature: settings.temperature.unwrap_or(DEFAULT_TEMPERATURE),
+                top_p: settings.top_p,
+                max_output_tokens: settings.max_output_tokens,
+                thinking_config: settings.thinking_budget.map(|thinking_budget| ThinkingConfig { thinking_budget }),
+            },
+            safety_settings: settings
+                .safety_threshold
+                .as_deref()
+                .map(|threshold| {
+                    SAFETY_CATEGORIES
+                        .iter()
+                        .map(|category| SafetySetting { category, threshold })
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+}
+
+// One server-sent event of a streamed GenerateContentResponse
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StreamEvent {
+    #[serde(default)]
+    candidates: Vec<Candidate>,
+    #[serde(default)]
+    prompt_feedback: Option<PromptFeedback>,
+    #[serde(default)]
+    error: Option<ApiError>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Candidate {
+    #[serde(default)]
+    content: Option<CandidateContent>,
+    #[serde(default)]
+    finish_reason: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct CandidateContent {
+    #[serde(default)]
+    parts: Vec<CandidatePart>,
+}
+
+#[derive(Deserialize)]
+struct CandidatePart {
+    #[serde(default)]
+    text: Option<String>,
+    // Thought summaries are not part of the translation
+    #[serde(default)]
+    thought: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PromptFeedback {
+    #[serde(default)]
+    block_reason: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ApiError {
+    #[serde(default)]
+    message: String,
+}
+
+pub struct GeminiProvider {
+    pub(crate) http: Arc<HttpPool>,
+}
+
+#[async_trait]
+impl TranslationProvider for GeminiProvider {
+    fn id(&self) -> &'static str {
+        "gemini"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Gemini"
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            requires_api_key: true,
+            accepts_access_token: false,
+            requires_model: false,
+            enabled_by_default: true,
+            supports_model_selection: true,
+            supports_auto_detect: true,
+            is_llm: true,
+        }
+    }
+
+    fn default_base_url(&self) -> &'static str {
+        DEFAULT_BASE_URL
+    }
+
+    async fn translate(&self, request: &TranslationRequest) -> Result<TranslationOutcome, String> {
+        let api_key = request
+            .settings
+            .api_key()
+            .ok_or_else(|| "Gemini APIキーが設定されていません".to_string())?;
+        println!("API Key length: {}", api_key.len());
+
+        let base_url = self.base_url(&request.settings);
+
+        // Use provided model or get the latest flash model
+        let model_name = match request.settings.model() {
+            Some(m) => {
+                println!("Using provided model: {}", m);
+                m.to_string()
+            }
+            None => {
+                println!("Getting latest flash model...");
+                let m = latest_flash_model(&self.http, &base_url, api_key).await.map_err(|e| {
+                    println!("Failed to get latest flash model: {}", e);
+                    e
+                })?;
+                println!("Latest flash model: {}", m);
+                m
+            }
+        };
+
+        // Streaming keeps long outputs from hitting a total-request timeout; only stalls fail
+        let client = self.http.streaming_client();
+        let url = format!(
+            "{}/{}/models/{}:streamGenerateContent?alt=sse&key={}",
+            base_url, API_VERSION, model_name, api_key
+        );
+
+        println!("Request URL: {}", url.replace(api_key, "***"));
+
+        let system_instruction = prompt::system_instruction(language_name(&request.target_lang));
+        let options: GeminiOptions = request.settings.options();
+        let generation = options.settings_for(&model_name);
+        let params = GenerateContentRequest::new(&system_instruction, &request.text, &generation);
+
+        if is_verbose() {
+            println!("Request params: {}", serde_json::to_string(&params).unwrap_or_default());
+        } else {
+            println!("Sending Gemini request (params hidden in non-verbose mode)");
+        }
+
+        // Retries (429/5xx) happen before any output is streamed, so they are safe to repeat
+        let (mut response, attempts) = tokio::time::timeout(
+            FIRST_RESPONSE_TIMEOUT,
+            send_with_retry(SERVICE, client.post(&url).json(&params)),
+        )
+        .await
+        .map_err(|_| stall_error())?
+        .map_err(|e| map_request_error(SERVICE, e))?;
+
+        println!("Response status: {}", response.status());
+        if !response.status().is_success() {
+            return Err(status_error("Gemini", response).await);
+        }
+
+        // Server-sent events: each `data:` line carries one partial GenerateContentResponse
+        let mut buffer: Vec<u8> = Vec::new();
+        let mut translated = String::new();
+        let mut blocked: Option<String> = None;
+        loop {
+            let chunk = tokio::time::timeout(CHUNK_TIMEOUT, response.chunk())
+                .await
+                .map_err(|_| stall_error())?
+                .map_err(|e| map_request_error(SERVICE, e))?;
+            let Some(chunk) = chunk else { break };
+            buffer.extend_from_slice(&chunk);
+
+            while let Some(pos) = buffer.iter().position(|b| *b == b'\n') {
+                let line: Vec<u8> = buffer.drain(..=pos).collect();
+                apply_sse_line(&line, &mut translated, &mut blocked, request)?;
+            }
+        }
+        // Last event may not be newline-terminated
+        if !buffer.is_empty() {
+            apply_sse_line(&buffer, &mut translated, &mut blocked, request)?;
+        }
+
+        if is_verbose() {
+            println!("Streamed response (first 300 chars): {}", truncate_chars(&translated, 300));
+        } else {
+            println!("Gemini response received (body hidden in non-verbose mode)");
+        }
+
+        if translated.trim().is_empty() {
+            let err = match blocked {
+                Some(reason) => format!("Geminiが応答をブロックしました ({})。安全性の設定を確認してください。", reason),
+                None => "Translation not found in Gemini response".to_string(),
+            };
+            println!("Error: {}", err);
+            return Err(err);
+        }
+        println!("Translation successful!");
+        Ok(self.outcome(translated, Some(model_name)).with_attempts(attempts))
+    }
+
+    async fn list_models(&self, settings: &ProviderSettings) -> Result<Vec<String>, String> {
+        let api_key = settings
+            .api_key()
+            .ok_or_else(|| "Gemini APIキーが設定されていません".to_string())?;
+        list_models(&self.http, &self.base_url(settings), api_key).await
+    }
+}
+
+// Append the text of one SSE line to the translation and forward it as a chunk; a safety
+// block is recorded in `blocked` so an empty result can say why
+fn apply_sse_line(
+    line: &[u8],
+    translated: &mut String,
+    blocked: &mut Option<String>,
+    request: &TranslationRequest,
+) -> Result<(), String> {
+    let line = String::from_utf8_lossy(line);
+    let Some(data) = line.trim().strip_prefix("data:") else {
+        return Ok(());
+    };
+    let data = data.trim();
+    if data.is_empty() {
+        return Ok(());
+    }
+
+    let event = serde_json::from_str::<StreamEvent>(data)
+        .map_err(|e| format!("Failed to parse Gemini stream: {}", e))?;
+    if let Some(error) = event.error {
+        return Err(format!("Gemini API error: {}", error.message));
+    }
+    if let Some(reason) = event.prompt_feedback.and_then(|feedback| feedback.block_reason) {
+        *blocked = Some(reason);
+    }
+
+    let Some(candidate) = event.candidates.into_iter().next() else {
+        return Ok(());
+    };
+    if let Some(reason) = candidate.finish_reason.filter(|r| matches!(r.as_str(), "SAFETY" | "PROHIBITED_CONTENT" | "BLOCKLIST")) {
+        *blocked = Some(reason);
+    }
+    let delta: String = candidate
+        .content
+        .map(|content| {
+            content
+                .parts
+                .into_iter()
+                .filter(|part| !part.thought)
+                .filter_map(|part| part.text)
+                .collect()
+        })
+        .unwrap_or_default();
+    if !delta.is_empty() {
+        translated.push_str(&delta);
+        request.emit_chunk(&delta, translated);
+    }
+    Ok(())
+}
+
+fn stall_error() -> String {
+    println!("Request error ({}): no data received in time", SERVICE);
+    format!("{}の応答が途中で止まりました。インターネット接続を確認してください。", SERVICE)
+}
+
+// List available Gemini models; falls back to a static list when the API is unavailable
+async fn list_models(http: &HttpPool, base_url: &str, api_key: &str) -> Result<Vec<String>, String> {
+    println!("Fetching Gemini models with key length: {}", api_key.len());
+    let client = http.client(10);
+    let url = format!("{}/{}/models?key={}", base_url, API_VERSION, api_key);
+
+    println!("Request URL: {}", url.replace(api_key, "***"));
+
+    let response = match send_with_retry(SERVICE, client.get(&url)).await {
+        Ok((response, _)) => response,
+        Err(e) => {
+            println!("Request failed: {} - Using fallback models", e);
+            return Ok(fallback_models());
+        }
+    };
+
+    let status = response.status();
+    println!("Response status: {}", status);
+
+    let body = match response.text().await {
+        Ok(body) => body,
+        Err(e) => {
+            if is_verbose() {
+                println!("Failed to read response body: {} - Using fallback models", e);
+            } else {
+                println!("Failed to read response body - Using fallback models");
+            }
+            return Ok(fallback_models());
+        }
+    };
+
+    if is_verbose() {
+        println!("Response body (first 500 chars): {}", truncate_chars(&body, 500));
+    } else {
+        println!("Gemini models response received (body hidden in non-verbose mode)");
+    }
+
+    if !status.is_success() {
+        if is_verbose() {
+            println!("API returned error {}: {} - Using fallback models", status, body);
+        } else {
+            println!("API returned error {} - Using fallback models", status);
+        }
+        return Ok(fallback_models());
+    }
+
+    let json = match serde_json::from_str::<serde_json::Value>(&body) {
+        Ok(json) => json,
+        Err(e) => {
+            if is_verbose() {
+                println!("Failed to parse JSON: {}", e);
+            } else {
+                println!("Failed to parse JSON");
+            }
+            return Ok(fallback_models());
+        }
+    };
+
+    let mut models = Vec::new();
+    if let Some(model_list) = json["models"].as_array() {
+        println!("Found {} models in response", model_list.len());
+        for model in model_list {
+            // Extract model name from "models/gemini-xxx" format
+            if let Some(model_name) = model["name"].as_str().and_then(|n| n.strip_prefix("models/")) {
+                println!("Adding model: {}", model_name);
+                models.push(model_name.to_string());
+            }
+        }
+    } else {
+        println!("No 'models' array found in response");
+    }
+    println!("Returning {} models", models.len());
+    Ok(models)
+}
+
+// Fallback list of commonly used Gemini models
+fn fallback_models() -> Vec<String> {
+    vec![
+        "gemini-2.0-flash-exp".to_string(),
+        "gemini-2.0-flash".to_string(),
+        "gemini-1.5-flash".to_string(),
+        "gemini-1.5-flash-8b".to_string(),
+        "gemini-1.5-pro".to_string(),
+        "gemini-pro".to_string(),
+    ]
+}
+
+// Pick the newest flash model (e.g., gemini-2.5-flash is newer than gemini-1.5-flash)
+async fn latest_flash_model(http: &HttpPool, base_url: &str, api_key: &str) -> Result<String, String> {
+    let models = list_models(http, base_url, api_key).await?;
+
+    models
+        .into_iter()
+        .filter(|m| m.contains("flash") && m.contains("gemini"))
+        .max()
+        .ok_or_else(|| "No flash models found".to_string())
+}
@@ -0,0 +1,407 @@
+// Google Translate Web API (no API key required, same as pot-app)
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+use super::segment::split_for_translation;
+use super::{
+    map_request_error, send_with_retry, status_error, DetectedLanguage, DictionaryEntry, DictionarySense,
+    DictionaryTerm, HttpPool, ProviderCapabilities, TimedClient, TranslatedSegment, TranslationOutcome,
+    TranslationProvider, TranslationRequest,
+};
+use crate::{is_verbose, truncate_chars};
+
+const SERVICE: &str = "Google翻訳";
+pub const DEFAULT_BASE_URL: &str = "https://translate.google.com";
+const TRANSLATE_PATH: &str = "/translate_a/single";
+// Longer selections are split at paragraph / sentence boundaries (see segment.rs)
+const MAX_CHUNK_CHARS: usize = 1800;
+const MAX_PARALLEL_CHUNKS: usize = 3;
+// Response parts for plain text (t) and for dictionary lookups: bd = alternative translations,
+// rm = transliteration, ex = example sentences, ss = synonyms
+const TEXT_DATA_TYPES: &[&str] = &["t"];
+const DICTIONARY_DATA_TYPES: &[&str] = &["t", "bd", "rm", "ex", "ss"];
+const DICTIONARY_MAX_CHARS: usize = 40;
+const DICTIONARY_MAX_WORDS: usize = 3;
+// Japanese / Chinese / Thai have no spaces to count words by, so only a few letters pass
+const DICTIONARY_MAX_UNSPACED_CHARS: usize = 8;
+const MAX_EXAMPLES: usize = 3;
+// The start of the text is enough to detect its language
+const DETECT_MAX_CHARS: usize = 500;
+
+pub struct GoogleWebProvider {
+    pub(crate) http: Arc<HttpPool>,
+}
+
+#[async_trait]
+impl TranslationProvider for GoogleWebProvider {
+    fn id(&self) -> &'static str {
+        "google"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Google (Free)"
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            requires_api_key: false,
+            accepts_access_token: false,
+            requires_model: false,
+            enabled_by_default: true,
+            supports_model_selection: false,
+            supports_auto_detect: true,
+            is_llm: false,
+        }
+    }
+
+    fn default_base_url(&self) -> &'static str {
+        DEFAULT_BASE_URL
+    }
+
+    // Long selections go out as several chunk requests
+    fn limits_own_requests(&self) -> bool {
+        true
+    }
+
+    async fn translate(&self, request: &TranslationRequest) -> Result<TranslationOutcome, String> {
+        let client = self.http.client(10);
+        let url = format!("{}{}", self.base_url(&request.settings), TRANSLATE_PATH);
+
+        let segmented = split_for_translation(&request.text, MAX_CHUNK_CHARS);
+        if segmented.chunks.is_empty() {
+            return Err("翻訳するテキストがありません".to_string());
+        }
+        if let [chunk] = segmented.chunks.as_slice() {
+            println!("Sending request to Google Translate...");
+            // Single words / short phrases also get dictionary data
+            let lookup = is_dictionary_lookup(&chunk.text);
+            let data_types = if lookup { DICTIONARY_DATA_TYPES } else { TEXT_DATA_TYPES };
+            let _rate = request.acquire_permit(self.id()).await?;
+            let (json, attempts) =
+                translate_chunk(&client, &url, &request.source_lang, &request.target_lang, &chunk.text, data_types)
+                    .await?;
+            let translation = sentences_text(&json)?;
+
+            let mut outcome = self
+                .outcome(segmented.reassemble(&[translation]), None)
+                .with_attempts(attempts);
+            outcome.detected_language = detected_language(&json);
+            outcome.segments = sentence_segments(&json);
+            if lookup {
+                outcome.dictionary = parse_dictionary(&json, &chunk.text);
+            }
+            return Ok(outcome);
+        }
+
+        println!(
+            "Sending {} chunks to Google Translate ({} chars)...",
+            segmented.chunks.len(),
+            request.text.chars().count()
+        );
+
+        // Chunks are translated concurrently; dropping the set on error aborts the rest.
+        // Each chunk is a request of its own for the rate limiter
+        let semaphore = Arc::new(Semaphore::new(MAX_PARALLEL_CHUNKS));
+        let mut tasks = JoinSet::new();
+        for (index, chunk) in segmented.chunks.iter().enumerate() {
+            let (client, url, semaphore) = (client.clone(), url.clone(), semaphore.clone());
+            let (source_lang, target_lang) = (request.source_lang.clone(), request.target_lang.clone());
+            let (text, limiter) = (chunk.text.clone(), request.limiter.clone());
+            let provider_id = self.id();
+            tasks.spawn(async move {
+                let _permit = semaphore.acquire_owned().await.map_err(|e| e.to_string())?;
+                let _rate = match &limiter {
+                    Some(limiter) => Some(limiter.acquire(provider_id).await?),
+                    None => None,
+                };
+                let (json, attempts) =
+                    translate_chunk(&client, &url, &source_lang, &target_lang, &text, TEXT_DATA_TYPES).await?;
+                sentences_text(&json).map(|translation| (index, (translation, attempts, json)))
+            });
+        }
+
+        // Completed chunks are reassembled in order, streaming each finished prefix to the popup
+        let mut translations: Vec<Option<String>> = vec![None; segmented.chunks.len()];
+        let mut responses: Vec<serde_json::Value> = vec![serde_json::Value::Null; segmented.chunks.len()];
+        let mut result = segmented.leading.clone();
+        let mut next = 0;
+        let mut attempts = 1;
+        while let Some(joined) = tasks.join_next().await {
+            let (index, (translation, chunk_attempts, json)) =
+                joined.map_err(|e| format!("Translation task failed: {}", e))??;
+            attempts = attempts.max(chunk_attempts);
+            translations[index] = Some(translation);
+            responses[index] = json;
+
+            while let Some(Some(translation)) = translations.get(next) {
+                let delta = format!("{}{}", translation, segmented.chunks[next].separator);
+                result.push_str(&delta);
+                request.emit_chunk(&delta, &result);
+                next += 1;
+            }
+        }
+
+        if is_verbose() {
+            println!("Final translation: {}", result);
+        }
+        let mut outcome = self.outcome(result, None).with_attempts(attempts);
+        // The first chunk holds the opening of the text, which is what detection is based on
+        outcome.detected_language = responses.first().and_then(detected_language);
+        outcome.segments = responses.iter().flat_map(sentence_segments).collect();
+        Ok(outcome)
+    }
+}
+
+// Translate one chunk and return the raw response; the text goes in a POST body so long
+// input is not limited by URL length
+async fn translate_chunk(
+    client: &TimedClient,
+    url: &str,
+    source_lang: &str,
+    target_lang: &str,
+    text: &str,
+    data_types: &[&str],
+) -> Result<(serde_json::Value, u32), String> {
+    let mut query = vec![
+        ("client", "gtx"),
+        ("sl", source_lang),
+        ("tl", target_lang),
+        ("hl", target_lang),
+        ("dj", "1"),
+        ("source", "input"),
+    ];
+    query.extend(data_types.iter().map(|dt| ("dt", *dt)));
+
+    let (response, attempts) = send_with_retry(SERVICE, client.post(url).query(&query).form(&[("q", text)]))
+        .await
+        .map_err(|e| map_request_error(SERVICE, e))?;
+
+    println!("Response status: {}", response.status());
+    if !response.status().is_success() {
+        return Err(status_error("Google Translate", response).await);
+    }
+
+    let json = response
+        .json::<serde_json::Value>()
+        .await
+        .map_err(|e| {
+            println!("Failed to parse JSON: {}", e);
+            format!("Failed to parse response: {}", e)
+        })?;
+
+    if is_verbose() {
+        println!("JSON: {}", serde_json::to_string_pretty(&json).unwrap_or_default());
+    } else {
+        println!("Received JSON response (details hidden in non-verbose mode)");
+    }
+    Ok((json, attempts))
+}
+
+// Concatenated `sentences[].trans` (the transliteration entry has no `trans` and is skipped)
+fn sentences_text(json: &serde_json::Value) -> Result<String, String> {
+    let Some(sentences) = json["sentences"].as_array() else {
+        println!("No 'sentences' field in JSON");
+        return Err("Translation not found in response".to_string());
+    };
+
+    println!("Found {} sentences", sentences.len());
+    let result: String = sentences
+        .iter()
+        .filter_map(|sentence| sentence["trans"].as_str())
+        .collect();
+
+    if result.is_empty() {
+        println!("No translation found in sentences");
+        return Err("Translation not found in response".to_string());
+    }
+    Ok(result)
+}
+
+// Source language Google detected (`src`) with its confidence (`ld_result`, else `confidence`)
+fn detected_language(json: &serde_json::Value) -> Option<DetectedLanguage> {
+    let language = json["src"].as_str().filter(|l| !l.is_empty())?;
+    let ld_result = &json["ld_result"];
+    let confidence = ld_result["srclangs"]
+        .as_array()
+        .and_then(|langs| langs.iter().position(|l| l.as_str() == Some(language)))
+        .and_then(|i| ld_result["srclangs_confidences"][i].as_f64())
+        .or_else(|| json["confidence"].as_f64())?;
+    Some(DetectedLanguage {
+        language: language.to_string(),
+        confidence,
+    })
+}
+
+// `sentences[]` as source / translation pairs
+fn sentence_segments(json: &serde_json::Value) -> Vec<TranslatedSegment> {
+    json["sentences"]
+        .as_array()
+        .map(|sentences| {
+            sentences
+                .iter()
+                .filter_map(|s| {
+                    Some(TranslatedSegment {
+                        source: s["orig"].as_str()?.to_string(),
+                        translation: s["trans"].as_str()?.to_string(),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// A single line of a few words (or a few unspaced letters, without a sentence end) is treated
+// as a dictionary lookup
+fn is_dictionary_lookup(text: &str) -> bool {
+    !text.contains('\n')
+        && text.chars().count() <= DICTIONARY_MAX_CHARS
+        && text.split_whitespace().count() <= DICTIONARY_MAX_WORDS
+        && text.chars().filter(|c| is_unspaced_letter(*c)).count() <= DICTIONARY_MAX_UNSPACED_CHARS
+        && !text.contains(['。', '！', '？', '、', '，'])
+}
+
+// Kana, Han and Thai letters
+fn is_unspaced_letter(c: char) -> bool {
+    matches!(
+        c,
+        '\u{3040}'..='\u{30FF}'
+            | '\u{31F0}'..='\u{31FF}'
+            | '\u{FF66}'..='\u{FF9F}'
+            | '\u{4E00}'..='\u{9FFF}'
+            | '\u{3400}'..='\u{4DBF}'
+            | '\u{F900}'..='\u{FAFF}'
+            | '\u{0E00}'..='\u{0E7F}'
+            | '々'
+    )
+}
+
+// Build a DictionaryEntry from the `dict` (bd), `sentences[].translit` (rm), `examples` (ex)
+// and `synsets` (ss) parts of a dj=1 response; None when Google returned no dictionary data
+fn parse_dictionary(json: &serde_json::Value, text: &str) -> Option<DictionaryEntry> {
+    let strings = |value: &serde_json::Value| -> Vec<String> {
+        value
+            .as_array()
+            .map(|items| items.iter().filter_map(|s| s.as_str()).map(str::to_string).collect())
+            .unwrap_or_default()
+    };
+    let non_empty = |value: &serde_json::Value| value.as_str().filter(|s| !s.is_empty()).map(str::to_string);
+    let empty = Vec::new();
+
+    let senses: Vec<DictionarySense> = json["dict"]
+        .as_array()
+        .unwrap_or(&empty)
+        .iter()
+        .map(|sense| DictionarySense {
+            part_of_speech: sense["pos"].as_str().unwrap_or_default().to_string(),
+            terms: match sense["entry"].as_array() {
+                Some(entries) => entries
+                    .iter()
+                    .filter_map(|entry| {
+                        Some(DictionaryTerm {
+                            text: entry["word"].as_str()?.to_string(),
+                            reverse_translations: strings(&entry["reverse_translation"]),
+                            score: entry["score"].as_f64(),
+                        })
+                    })
+                    .collect(),
+                // Older responses only carry the bare term list
+                None => strings(&sense["terms"])
+                    .into_iter()
+                    .map(|text| DictionaryTerm { text, ..Default::default() })
+                    .collect(),
+            },
+        })
+        .filter(|sense| !sense.terms.is_empty())
+        .collect();
+
+    let synonyms: Vec<DictionarySense> = json["synsets"]
+        .as_array()
+        .unwrap_or(&empty)
+        .iter()
+        .map(|synset| {
+            let mut words: Vec<String> = Vec::new();
+            for entry in synset["entry"].as_array().unwrap_or(&empty) {
+                for word in strings(&entry["synonym"]) {
+                    if !words.contains(&word) {
+                        words.push(word);
+                    }
+                }
+            }
+            DictionarySense {
+                part_of_speech: synset["pos"].as_str().unwrap_or_default().to_string(),
+                terms: words.into_iter().map(|text| DictionaryTerm { text, ..Default::default() }).collect(),
+            }
+        })
+        .filter(|sense| !sense.terms.is_empty())
+        .collect();
+
+    // Examples mark the headword with <b>…</b>
+    let examples: Vec<String> = json["examples"]["example"]
+        .as_array()
+        .unwrap_or(&empty)
+        .iter()
+        .filter_map(|example| example["text"].as_str())
+        .map(|text| text.replace("<b>", "").replace("</b>", ""))
+        .take(MAX_EXAMPLES)
+        .collect();
+
+    let translit = json["sentences"]
+        .as_array()
+        .and_then(|sentences| sentences.iter().find(|s| s.get("translit").is_some() || s.get("src_translit").is_some()));
+    let source_transliteration = translit.and_then(|s| non_empty(&s["src_translit"]));
+    let transliteration = translit.and_then(|s| non_empty(&s["translit"]));
+
+    if senses.is_empty() && synonyms.is_empty() && examples.is_empty() {
+        return None;
+    }
+
+    let base_form = json["dict"][0]["base_form"]
+        .as_str()
+        .or_else(|| json["synsets"][0]["base_form"].as_str())
+        .filter(|s| !s.is_empty());
+    Some(DictionaryEntry {
+        word: base_form.unwrap_or(text.trim()).to_string(),
+        source_transliteration,
+        transliteration,
+        senses,
+        synonyms,
+        examples,
+    })
+}
+
+// Detect the language of `text` via the same web endpoint (no API key required)
+pub async fn detect_language(http: &HttpPool, base_url: &str, text: &str) -> Result<String, String> {
+    let client = http.client(8);
+    let url = format!("{}{}", base_url, TRANSLATE_PATH);
+
+    let (response, _) = send_with_retry(
+        SERVICE,
+        client
+            .post(&url)
+            .query(&[("client", "gtx"), ("sl", "auto"), ("tl", "en"), ("dt", "t")])
+            .form(&[("q", truncate_chars(text, DETECT_MAX_CHARS))]),
+    )
+    .await
+    .map_err(|e| map_request_error("言語検出", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("Detection request failed with status: {}", response.status()));
+    }
+
+    let body = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read response: {}", e))?;
+
+    // Parse the response array to extract detected language
+    // Response format: [[["translation",...],...],...,"detected_lang",...]
+    // The detected language is usually at index 2
+    serde_json::from_str::<serde_json::Value>(&body)
+        .ok()
+        .and_then(|json| json.get(2).and_then(|lang| lang.as_str()).map(str::to_string))
+        .ok_or_else(|| "Failed to detect language from response".to_string())
+}
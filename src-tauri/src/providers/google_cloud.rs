@@ -0,0 +1,286 @@
+// Official Google Cloud Translation API (Basic v2 and Advanced v3 with glossaries)
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    langdetect, map_request_error, send_with_retry, status_error, DetectedLanguage, HttpPool,
+    ProviderCapabilities, TimedClient, TranslationOutcome, TranslationProvider, TranslationRequest,
+};
+use crate::is_verbose;
+
+const SERVICE: &str = "Google Cloud Translation";
+const DEFAULT_BASE_URL: &str = "https://translation.googleapis.com";
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct GoogleCloudOptions {
+    // "v2" (default) or "v3"
+    version: Option<String>,
+    // v3 only
+    project_id: Option<String>,
+    // v3 only; glossaries require a regional location such as "us-central1"
+    location: Option<String>,
+    glossary_id: Option<String>,
+}
+
+#[derive(Serialize)]
+struct V2Request<'a> {
+    q: &'a str,
+    target: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source: Option<&'a str>,
+    format: &'a str,
+}
+
+#[derive(Deserialize)]
+struct V2Response {
+    data: V2Data,
+}
+
+#[derive(Deserialize)]
+struct V2Data {
+    translations: Vec<V2Translation>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct V2Translation {
+    translated_text: String,
+    #[serde(default)]
+    detected_source_language: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct V3Request<'a> {
+    contents: [&'a str; 1],
+    target_language_code: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source_language_code: Option<&'a str>,
+    mime_type: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    glossary_config: Option<V3GlossaryConfig>,
+}
+
+#[derive(Serialize)]
+struct V3GlossaryConfig {
+    glossary: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct V3Response {
+    #[serde(default)]
+    translations: Vec<V3Translation>,
+    #[serde(default)]
+    glossary_translations: Vec<V3Translation>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct V3Translation {
+    translated_text: String,
+    #[serde(default)]
+    detected_language_code: Option<String>,
+}
+
+// How requests are authenticated: API key, or an OAuth access token (e.g. `gcloud auth print-access-token`)
+#[derive(Clone, Copy)]
+enum Credential<'a> {
+    ApiKey(&'a str),
+    AccessToken(&'a str),
+}
+
+impl Credential<'_> {
+    fn apply(self, builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match self {
+            Credential::ApiKey(api_key) => builder.query(&[("key", api_key)]),
+            Credential::AccessToken(token) => builder.bearer_auth(token),
+        }
+    }
+}
+
+pub struct GoogleCloudProvider {
+    pub(crate) http: Arc<HttpPool>,
+}
+
+impl GoogleCloudProvider {
+    async fn translate_v2(
+        &self,
+        client: &TimedClient,
+        base_url: &str,
+        credential: Credential<'_>,
+        request: &TranslationRequest,
+    ) -> Result<(String, Option<String>, u32), String> {
+        let url = format!("{}/language/translate/v2", base_url);
+        let body = V2Request {
+            q: &request.text,
+            target: &request.target_lang,
+            source: (request.source_lang != "auto").then_some(request.source_lang.as_str()),
+            format: "text",
+        };
+
+        let builder = credential.apply(client.post(&url).json(&body));
+        let (response, attempts) = send_with_retry(SERVICE, builder)
+            .await
+            .map_err(|e| map_request_error(SERVICE, e))?;
+
+        println!("Response status: {}", response.status());
+        if !response.status().is_success() {
+            return Err(status_error(SERVICE, response).await);
+        }
+
+        let parsed = response
+            .json::<V2Response>()
+            .await
+            .map_err(|e| format!("Failed to parse Cloud Translation v2 response: {}", e))?;
+
+        parsed
+            .data
+            .translations
+            .into_iter()
+            .next()
+            .map(|t| (t.translated_text, t.detected_source_language, attempts))
+            .ok_or_else(|| "Translation not found in Cloud Translation response".to_string())
+    }
+
+    async fn translate_v3(
+        &self,
+        client: &TimedClient,
+        base_url: &str,
+        credential: Credential<'_>,
+        options: &GoogleCloudOptions,
+        request: &TranslationRequest,
+    ) -> Result<(String, Option<String>, u32), String> {
+        let project_id = options
+            .project_id
+            .as_deref()
+            .filter(|p| !p.trim().is_empty())
+            .ok_or_else(|| "Cloud Translation v3にはプロジェクトIDが必要です".to_string())?;
+        let location = options
+            .location
+            .as_deref()
+            .filter(|l| !l.trim().is_empty())
+            .unwrap_or("global");
+        let parent = format!("projects/{}/locations/{}", project_id, location);
+        let url = format!("{}/v3/{}:translateText", base_url, parent);
+
+        let glossary_id = options.glossary_id.as_deref().map(str::trim).filter(|g| !g.is_empty());
+        // v3 rejects a glossary without a source language: an unknown source is detected locally,
+        // and when that is not confident the glossary is left out
+        let source_lang = match request.source_lang.as_str() {
+            "auto" if glossary_id.is_some() => langdetect::detect(&request.text)
+                .into_iter()
+                .next()
+                .filter(|best| best.confidence >= langdetect::CONFIDENT)
+                .map(|best| best.language),
+            "auto" => None,
+            source => Some(source.to_string()),
+        };
+        let glossary_config = glossary_id
+            .filter(|_| source_lang.is_some())
+            .map(|g| V3GlossaryConfig { glossary: format!("{}/glossaries/{}", parent, g) });
+        if glossary_id.is_some() && glossary_config.is_none() {
+            println!("Source language is unknown; translating without the glossary");
+        }
+
+        let body = V3Request {
+            contents: [request.text.as_str()],
+            target_language_code: &request.target_lang,
+            source_language_code: source_lang.as_deref(),
+            mime_type: "text/plain",
+            glossary_config,
+        };
+
+        let builder = credential.apply(client.post(&url).json(&body));
+
+        let (response, attempts) = send_with_retry(SERVICE, builder)
+            .await
+            .map_err(|e| map_request_error(SERVICE, e))?;
+
+        println!("Response status: {}", response.status());
+        if !response.status().is_success() {
+            return Err(status_error(SERVICE, response).await);
+        }
+
+        let parsed = response
+            .json::<V3Response>()
+            .await
+            .map_err(|e| format!("Failed to parse Cloud Translation v3 response: {}", e))?;
+
+        // Prefer the glossary-applied translation when one was requested
+        parsed
+            .glossary_translations
+            .into_iter()
+            .chain(parsed.translations)
+            .next()
+            .map(|t| (t.translated_text, t.detected_language_code, attempts))
+            .ok_or_else(|| "Translation not found in Cloud Translation response".to_string())
+    }
+}
+
+#[async_trait]
+impl TranslationProvider for GoogleCloudProvider {
+    fn id(&self) -> &'static str {
+        "google-cloud"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Google Cloud"
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            requires_api_key: true,
+            accepts_access_token: true,
+            requires_model: false,
+            enabled_by_default: true,
+            supports_model_selection: false,
+            supports_auto_detect: true,
+            is_llm: false,
+        }
+    }
+
+    fn default_base_url(&self) -> &'static str {
+        DEFAULT_BASE_URL
+    }
+
+    async fn translate(&self, request: &TranslationRequest) -> Result<TranslationOutcome, String> {
+        let options: GoogleCloudOptions = request.settings.options();
+        let use_v3 = options.version.as_deref() == Some("v3");
+        // An OAuth access token, when set, is used instead of the API key
+        let credential = match (request.settings.access_token(), request.settings.api_key()) {
+            (Some(token), _) => Credential::AccessToken(token),
+            (None, Some(api_key)) => Credential::ApiKey(api_key),
+            (None, None) => return Err("Google Cloud APIキーが設定されていません".to_string()),
+        };
+
+        let client = self.http.client(10);
+        let base_url = self.base_url(&request.settings);
+
+        println!("Sending request to Cloud Translation {}...", if use_v3 { "v3" } else { "v2" });
+
+        let (translated, detected, attempts) = if use_v3 {
+            self.translate_v3(&client, &base_url, credential, &options, request).await?
+        } else {
+            self.translate_v2(&client, &base_url, credential, request).await?
+        };
+
+        if translated.is_empty() {
+            return Err("Translation not found in Cloud Translation response".to_string());
+        }
+        if is_verbose() {
+            println!("Final translation: {}", translated);
+        }
+
+        let mut outcome = self
+            .outcome(translated, Some(if use_v3 { "v3" } else { "v2" }.to_string()))
+            .with_attempts(attempts);
+        // Cloud Translation does not report a confidence score for detection
+        outcome.detected_language = detected.map(|language| DetectedLanguage { language, confidence: 1.0 });
+        Ok(outcome)
+    }
+}
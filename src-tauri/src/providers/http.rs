@@ -0,0 +1,202 @@
+// One pooled HTTP client shared by every provider: connections (DNS, TCP, TLS) are kept alive
+// between hotkey presses, and HTTP/2 is negotiated via ALPN where the server supports it.
+
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::{Mutex, RwLock};
+use std::time::Duration;
+
+use reqwest::{Certificate, IntoUrl, NoProxy, Proxy, RequestBuilder};
+use serde::Deserialize;
+
+// How many recently used endpoints are pre-connected when the hotkey fires
+const MAX_WARM_ORIGINS: usize = 4;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProxyMode {
+    // Environment variables (HTTP_PROXY, HTTPS_PROXY, ALL_PROXY, NO_PROXY) and OS settings
+    #[default]
+    System,
+    // Always connect directly
+    None,
+    Manual,
+}
+
+// Network settings from the frontend (persisted in localStorage), applied to every provider
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct NetworkSettings {
+    pub proxy_mode: ProxyMode,
+    // http://, https://, socks5:// or socks5h:// (DNS resolved by the proxy)
+    pub proxy_url: Option<String>,
+    pub proxy_username: Option<String>,
+    pub proxy_password: Option<String>,
+    // Comma-separated hosts, domains (".corp.example") or CIDRs that bypass the proxy
+    pub no_proxy: Option<String>,
+    // PEM files with extra trusted root certificates (e.g. a TLS-intercepting proxy's CA)
+    pub ca_cert_paths: Vec<String>,
+}
+
+pub struct HttpPool {
+    client: RwLock<reqwest::Client>,
+    // Most recently used first
+    recent_origins: Mutex<VecDeque<String>>,
+}
+
+impl HttpPool {
+    pub fn new() -> Result<Self, String> {
+        Ok(Self {
+            client: RwLock::new(build_pooled_client(&NetworkSettings::default())?),
+            recent_origins: Mutex::new(VecDeque::new()),
+        })
+    }
+
+    // Rebuild the shared client with new proxy/certificate settings.
+    // On error the previous client stays in use.
+    pub fn configure(&self, settings: &NetworkSettings) -> Result<(), String> {
+        let client = build_pooled_client(settings)?;
+        match self.client.write() {
+            Ok(mut current) => *current = client,
+            Err(poisoned) => *poisoned.into_inner() = client,
+        }
+        println!("Network settings applied (proxy: {:?}, extra CAs: {})", settings.proxy_mode, settings.ca_cert_paths.len());
+        Ok(())
+    }
+
+    fn shared(&self) -> reqwest::Client {
+        // reqwest::Client is a handle to the pool, so cloning it is cheap
+        match self.client.read() {
+            Ok(client) => client.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
+    // Shared client whose requests time out after `timeout_secs` (per-provider timeouts)
+    pub fn client(&self, timeout_secs: u64) -> TimedClient {
+        TimedClient {
+            client: self.shared(),
+            timeout: Duration::from_secs(timeout_secs),
+        }
+    }
+
+    // Shared client without a total timeout, for streaming responses (callers bound each chunk)
+    pub fn streaming_client(&self) -> reqwest::Client {
+        self.shared()
+    }
+
+    // Remember an endpoint so the next hotkey press can pre-connect to it
+    pub fn remember_origin(&self, base_url: &str) {
+        let Some(origin) = origin_of(base_url) else { return };
+        if let Ok(mut origins) = self.recent_origins.lock() {
+            origins.retain(|o| *o != origin);
+            origins.push_front(origin);
+            origins.truncate(MAX_WARM_ORIGINS);
+        }
+    }
+
+    // Open connections to recently used endpoints so the first translation skips DNS/TCP/TLS setup.
+    // The returned future is meant to be spawned; failures are only logged.
+    pub fn warm_up(&self, default_base_url: &str) -> impl Future<Output = ()> + Send + 'static {
+        let mut origins: Vec<String> = self
+            .recent_origins
+            .lock()
+            .map(|o| o.iter().cloned().collect())
+            .unwrap_or_default();
+        if origins.is_empty() {
+            origins.extend(origin_of(default_base_url));
+        }
+
+        let client = self.shared();
+        async move {
+            for origin in origins {
+                let result = client
+                    .head(&origin)
+                    .timeout(Duration::from_secs(3))
+                    .send()
+                    .await;
+                if let Err(e) = result {
+                    println!("Warm-up of {} failed: {}", origin, e);
+                }
+            }
+        }
+    }
+}
+
+// Client handle with a per-provider request timeout applied to every request it builds
+#[derive(Clone)]
+pub struct TimedClient {
+    client: reqwest::Client,
+    timeout: Duration,
+}
+
+impl TimedClient {
+    pub fn get<U: IntoUrl>(&self, url: U) -> RequestBuilder {
+        self.client.get(url).timeout(self.timeout)
+    }
+
+    pub fn post<U: IntoUrl>(&self, url: U) -> RequestBuilder {
+        self.client.post(url).timeout(self.timeout)
+    }
+}
+
+fn build_pooled_client(settings: &NetworkSettings) -> Result<reqwest::Client, String> {
+    let mut builder = reqwest::Client::builder()
+        .connect_timeout(Duration::from_secs(10))
+        .pool_idle_timeout(Duration::from_secs(90))
+        .tcp_keepalive(Duration::from_secs(60));
+
+    // ProxyMode::System is reqwest's default behaviour
+    match settings.proxy_mode {
+        ProxyMode::System => {}
+        ProxyMode::None => builder = builder.no_proxy(),
+        ProxyMode::Manual => builder = builder.proxy(manual_proxy(settings)?),
+    }
+
+    for path in settings.ca_cert_paths.iter().map(|p| p.trim()).filter(|p| !p.is_empty()) {
+        for cert in load_pem_certificates(path)? {
+            builder = builder.add_root_certificate(cert);
+        }
+    }
+
+    builder
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))
+}
+
+fn manual_proxy(settings: &NetworkSettings) -> Result<Proxy, String> {
+    let url = settings
+        .proxy_url
+        .as_deref()
+        .map(str::trim)
+        .filter(|u| !u.is_empty())
+        .ok_or_else(|| "プロキシのURLが設定されていません".to_string())?;
+    let mut proxy = Proxy::all(url).map_err(|e| format!("プロキシのURLが不正です: {}", e))?;
+
+    if let Some(username) = settings.proxy_username.as_deref().filter(|u| !u.is_empty()) {
+        proxy = proxy.basic_auth(username, settings.proxy_password.as_deref().unwrap_or(""));
+    }
+    let no_proxy = settings.no_proxy.as_deref().and_then(NoProxy::from_string);
+    Ok(proxy.no_proxy(no_proxy))
+}
+
+// A PEM file may hold a single certificate or a bundle
+fn load_pem_certificates(path: &str) -> Result<Vec<Certificate>, String> {
+    let pem = std::fs::read(path).map_err(|e| format!("証明書ファイルを読み込めません ({}): {}", path, e))?;
+    let certs = Certificate::from_pem_bundle(&pem)
+        .map_err(|e| format!("証明書ファイルの形式が不正です ({}): {}", path, e))?;
+    if certs.is_empty() {
+        return Err(format!("証明書ファイルに証明書がありません ({})", path));
+    }
+    Ok(certs)
+}
+
+// "https://api.example.com/v1/x" -> "https://api.example.com"
+fn origin_of(url: &str) -> Option<String> {
+    let url = reqwest::Url::parse(url).ok()?;
+    let host = url.host_str()?;
+    Some(match url.port() {
+        Some(port) => format!("{}://{}:{}", url.scheme(), host, port),
+        None => format!("{}://{}", url.scheme(), host),
+    })
+}
@@ -0,0 +1,350 @@
+// Offline language detection: scripts decide most languages outright (kana → ja, Hangul → ko,
+// Cyrillic → ru, ...), Han without kana leans to zh, and Latin-script text is told apart by
+// common function words and diacritics. Cheap enough to run on every hotkey press.
+
+use serde::Serialize;
+
+use super::segment::split_sentences;
+use super::DetectedLanguage;
+
+// Below this, callers should confirm with a network detector
+pub const CONFIDENT: f64 = 0.8;
+
+// Share of kana among CJK characters above which Han is counted as Japanese
+const KANA_SHARE_FOR_JA: f64 = 0.05;
+
+// Kana is written in no other language, so it settles Japanese even when most letters are Latin
+// (identifiers, product names in technical text)
+const KANA_CONFIDENCE: f64 = 0.95;
+
+const LATIN_PROFILES: &[(&str, &[&str], &[char])] = &[
+    (
+        "en",
+        &[
+            "the", "and", "is", "are", "of", "to", "in", "that", "it", "for", "with", "this", "was", "you",
+            "have", "be", "not", "on", "as", "at",
+        ],
+        &[],
+    ),
+    (
+        "fr",
+        &[
+            "le", "la", "les", "et", "est", "des", "une", "un", "du", "que", "qui", "pas", "pour", "dans",
+            "ce", "il", "je", "vous", "sur", "avec",
+        ],
+        &['ç', 'à', 'è', 'ê', 'ë', 'î', 'ï', 'ô', 'œ', 'ù', 'û'],
+    ),
+    (
+        "de",
+        &[
+            "der", "die", "das", "und", "ist", "nicht", "ein", "eine", "ich", "zu", "den", "mit", "von",
+            "sie", "es", "auf", "für", "dem", "sich", "auch",
+        ],
+        &['ß', 'ä', 'ö', 'ü'],
+    ),
+    (
+        "es",
+        &[
+            "el", "la", "los", "las", "y", "es", "que", "de", "en", "un", "una", "por", "con", "para", "no",
+            "se", "del", "al", "lo", "como",
+        ],
+        &['ñ', '¿', '¡', 'á', 'í', 'ó', 'ú'],
+    ),
+    (
+        "it",
+        &[
+            "il", "la", "che", "di", "e", "è", "un", "una", "per", "non", "con", "sono", "del", "della",
+            "gli", "le", "si", "ma", "come", "anche",
+        ],
+        &['ì', 'ò'],
+    ),
+    (
+        "pt",
+        &[
+            "o", "a", "os", "as", "e", "é", "que", "de", "um", "uma", "para", "com", "não", "em", "do", "da",
+            "no", "na", "se", "por",
+        ],
+        &['ã', 'õ', 'â'],
+    ),
+    (
+        "nl",
+        &[
+            "de", "het", "een", "en", "is", "van", "dat", "niet", "ik", "je", "op", "te", "met", "zijn",
+            "voor", "die", "er", "maar", "ook", "wat",
+        ],
+        &['ĳ'],
+    ),
+];
+
+#[derive(Debug, Default)]
+struct ScriptCounts {
+    kana: usize,
+    han: usize,
+    hangul: usize,
+    latin: usize,
+    cyrillic: usize,
+    // Ukrainian-only Cyrillic letters (і ї є ґ)
+    ukrainian: usize,
+    other: Vec<(&'static str, usize)>,
+}
+
+impl ScriptCounts {
+    fn of(text: &str) -> Self {
+        let mut counts = ScriptCounts::default();
+        for c in text.chars() {
+            match c {
+                '\u{3040}'..='\u{30FF}' | '\u{31F0}'..='\u{31FF}' | '\u{FF66}'..='\u{FF9F}' => counts.kana += 1,
+                // The prolonged sound mark ー is in the katakana block above; 々 repeats kanji
+                '\u{4E00}'..='\u{9FFF}' | '\u{3400}'..='\u{4DBF}' | '\u{F900}'..='\u{FAFF}' | '々' => {
+                    counts.han += 1
+                }
+                '\u{AC00}'..='\u{D7AF}' | '\u{1100}'..='\u{11FF}' | '\u{3130}'..='\u{318F}' => counts.hangul += 1,
+                'і' | 'ї' | 'є' | 'ґ' | 'І' | 'Ї' | 'Є' | 'Ґ' => {
+                    counts.cyrillic += 1;
+                    counts.ukrainian += 1;
+                }
+                '\u{0400}'..='\u{04FF}' => counts.cyrillic += 1,
+                '\u{0600}'..='\u{06FF}' => counts.add_other("ar"),
+                '\u{0E00}'..='\u{0E7F}' => counts.add_other("th"),
+                '\u{0900}'..='\u{097F}' => counts.add_other("hi"),
+                '\u{0370}'..='\u{03FF}' => counts.add_other("el"),
+                '\u{0590}'..='\u{05FF}' => counts.add_other("he"),
+                c if c.is_alphabetic() && (c.is_ascii() || ('\u{00C0}'..='\u{024F}').contains(&c)) => {
+                    counts.latin += 1
+                }
+                _ => {}
+            }
+        }
+        counts
+    }
+
+    fn add_other(&mut self, language: &'static str) {
+        match self.other.iter_mut().find(|(l, _)| *l == language) {
+            Some((_, n)) => *n += 1,
+            None => self.other.push((language, 1)),
+        }
+    }
+
+    fn total(&self) -> usize {
+        let other: usize = self.other.iter().map(|(_, n)| n).sum();
+        self.kana + self.han + self.hangul + self.latin + self.cyrillic + other
+    }
+}
+
+// Ranked candidates (most likely first); empty when the text has no letters at all
+pub fn detect(text: &str) -> Vec<DetectedLanguage> {
+    let counts = ScriptCounts::of(text);
+    let total = counts.total();
+    if total == 0 {
+        return Vec::new();
+    }
+    let share = |n: usize| n as f64 / total as f64;
+
+    let mut scores: Vec<(&str, f64)> = Vec::new();
+    let cjk = counts.kana + counts.han;
+    if cjk > 0 {
+        if counts.kana as f64 / cjk as f64 >= KANA_SHARE_FOR_JA {
+            scores.push(("ja", share(cjk)));
+        } else {
+            // Kanji-only snippets ("日本語", "東京都") are common in Japanese too
+            let ja_share = if counts.han <= 4 { 0.35 } else { 0.1 };
+            scores.push(("zh", share(cjk) * (1.0 - ja_share)));
+            scores.push(("ja", share(cjk) * ja_share));
+        }
+    }
+    if counts.hangul > 0 {
+        scores.push(("ko", share(counts.hangul)));
+    }
+    if counts.cyrillic > 0 {
+        let language = if counts.ukrainian > 0 { "uk" } else { "ru" };
+        scores.push((language, share(counts.cyrillic)));
+    }
+    for (language, n) in &counts.other {
+        scores.push((language, share(*n)));
+    }
+    if counts.latin > 0 {
+        for (language, weight) in latin_weights(text) {
+            scores.push((language, share(counts.latin) * weight));
+        }
+    }
+
+    // Very short input is never fully trusted
+    let length_factor = (0.6 + total as f64 / 25.0).min(1.0);
+    let mut ranked: Vec<DetectedLanguage> = scores
+        .into_iter()
+        .map(|(language, score)| {
+            let mut confidence = score * length_factor;
+            if language == "ja" && counts.kana > 0 {
+                confidence = confidence.max(KANA_CONFIDENCE);
+            }
+            DetectedLanguage { language: language.to_string(), confidence: (confidence * 100.0).round() / 100.0 }
+        })
+        .filter(|d| d.confidence > 0.0)
+        .collect();
+    ranked.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+    ranked
+}
+
+// Writing system of a language code returned by `detect` (Japanese and Chinese share "cjk")
+pub fn script(language: &str) -> &'static str {
+    match language {
+        "ja" | "zh" => "cjk",
+        "ko" => "hangul",
+        "ru" | "uk" => "cyrillic",
+        "ar" => "arabic",
+        "th" => "thai",
+        "hi" => "devanagari",
+        "el" => "greek",
+        "he" => "hebrew",
+        _ => "latin",
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LabeledSegment {
+    pub text: String,
+    // Whitespace that followed the segment in the original text
+    pub separator: String,
+    // None for segments without letters (numbers, symbols)
+    pub language: Option<String>,
+    pub confidence: f64,
+}
+
+// Label each sentence (or line) of mixed-language text with its most likely language
+pub fn detect_segments(text: &str) -> Vec<LabeledSegment> {
+    split_sentences(text)
+        .chunks
+        .into_iter()
+        .map(|chunk| {
+            let best = detect(&chunk.text).into_iter().next();
+            LabeledSegment {
+                confidence: best.as_ref().map(|d| d.confidence).unwrap_or(0.0),
+                language: best.map(|d| d.language),
+                text: chunk.text,
+                separator: chunk.separator,
+            }
+        })
+        .collect()
+}
+
+// Likelihood of each Latin-script language relative to the closest alternative (1.0 when only
+// one language has any evidence)
+fn latin_weights(text: &str) -> Vec<(&'static str, f64)> {
+    let lower = text.to_lowercase();
+    let words: Vec<&str> = lower
+        .split(|c: char| !c.is_alphabetic() && c != '\'')
+        .filter(|w| !w.is_empty())
+        .collect();
+
+    let mut scores: Vec<(&'static str, f64)> = LATIN_PROFILES
+        .iter()
+        .map(|(language, stopwords, marks)| {
+            // A word shared by several languages ("is", "la", "de") is weak evidence for each
+            let word_hits: f64 = words
+                .iter()
+                .filter(|w| stopwords.contains(w))
+                .map(|w| 1.0 / LATIN_PROFILES.iter().filter(|(_, other, _)| other.contains(w)).count() as f64)
+                .sum();
+            let mark_hits = marks.iter().filter(|m| lower.contains(**m)).count() as f64;
+            (*language, word_hits + 2.0 * mark_hits)
+        })
+        .collect();
+
+    // Without any evidence (code, product names, terse notes) English is the best guess,
+    // but not a confident one
+    if scores.iter().all(|(_, s)| *s == 0.0) {
+        return vec![("en", 0.6)];
+    }
+    if let Some((_, en)) = scores.iter_mut().find(|(l, _)| *l == "en") {
+        *en += 0.5;
+    }
+
+    scores.sort_by(|a, b| b.1.total_cmp(&a.1));
+    let top_two = scores[0].1 + scores[1].1;
+    scores.iter_mut().for_each(|(_, s)| *s /= top_two);
+    scores.retain(|(_, s)| *s > 0.0);
+    scores
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn best(text: &str) -> (String, f64) {
+        let best = detect(text).into_iter().next().expect("no language detected");
+        (best.language, best.confidence)
+    }
+
+    #[test]
+    fn kana_is_confident_japanese() {
+        let (language, confidence) = best("これはペンです");
+        assert_eq!(language, "ja");
+        assert!(confidence >= CONFIDENT);
+    }
+
+    #[test]
+    fn kana_outweighs_latin_identifiers() {
+        for text in ["この関数は getUserName を呼び出します", "npm install でエラー", "OK です"] {
+            let (language, confidence) = best(text);
+            assert_eq!(language, "ja", "{}", text);
+            assert!(confidence >= CONFIDENT, "{}: {}", text, confidence);
+        }
+    }
+
+    #[test]
+    fn han_without_kana_leans_chinese() {
+        assert_eq!(best("我们今天去北京吃饭").0, "zh");
+        // Short kanji-only words are also common in Japanese: never confident
+        let candidates = detect("東京都");
+        assert_eq!(candidates[0].language, "zh");
+        assert!(candidates[0].confidence < CONFIDENT);
+        assert!(candidates.iter().any(|d| d.language == "ja"));
+    }
+
+    #[test]
+    fn other_scripts_decide_the_language() {
+        assert_eq!(best("안녕하세요, 반갑습니다").0, "ko");
+        assert_eq!(best("Привет, как дела?").0, "ru");
+        assert_eq!(best("Привіт, як справи?").0, "uk");
+        assert_eq!(best("مرحبا بالعالم").0, "ar");
+        assert_eq!(best("Καλημέρα κόσμε").0, "el");
+    }
+
+    #[test]
+    fn latin_languages_by_function_words_and_diacritics() {
+        for text in ["The weather is nice and I want to go for a walk with you.", "This is a pen and that is the book."] {
+            let (language, confidence) = best(text);
+            assert_eq!(language, "en", "{}", text);
+            assert!(confidence >= CONFIDENT, "{}: {}", text, confidence);
+        }
+        assert_eq!(best("Das ist nicht gut, aber ich bin so müde.").0, "de");
+        assert_eq!(best("Je pense que c'est une bonne idée pour nous.").0, "fr");
+        assert_eq!(best("¿Dónde está la estación de tren?").0, "es");
+    }
+
+    #[test]
+    fn guesses_stay_below_the_confidence_threshold() {
+        // No function words: English is only a guess, so callers confirm it with a network
+        // detector
+        for text in ["Guten Morgen!", "getUserName", "Hello world"] {
+            let (language, confidence) = best(text);
+            assert_eq!(language, "en", "{}", text);
+            assert!(confidence < CONFIDENT, "{}: {}", text, confidence);
+        }
+    }
+
+    #[test]
+    fn text_without_letters_has_no_candidates() {
+        assert!(detect("").is_empty());
+        assert!(detect("12:30 — 3.14 !?").is_empty());
+    }
+
+    #[test]
+    fn segments_keep_their_separators() {
+        let segments = detect_segments("これはペンです。\nThis is a pen and that is the book.");
+        let languages: Vec<_> = segments.iter().map(|s| s.language.as_deref()).collect();
+        assert_eq!(languages, [Some("ja"), Some("en")]);
+        let rebuilt: String = segments.iter().map(|s| format!("{}{}", s.text, s.separator)).collect();
+        assert_eq!(rebuilt, "これはペンです。\nThis is a pen and that is the book.");
+    }
+}
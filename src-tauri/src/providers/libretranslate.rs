@@ -0,0 +1,172 @@
+// LibreTranslate API (self-hostable machine translation, optional API key)
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    map_request_error, send_with_retry, status_error, DetectedLanguage, HttpPool,
+    ProviderCapabilities, ProviderSettings, SupportedLanguage, TranslationOutcome,
+    TranslationProvider, TranslationRequest,
+};
+use crate::is_verbose;
+
+const SERVICE: &str = "LibreTranslate";
+const DEFAULT_BASE_URL: &str = "http://localhost:5000";
+
+#[derive(Serialize)]
+struct TranslateRequest<'a> {
+    q: &'a str,
+    source: &'a str,
+    target: &'a str,
+    format: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    api_key: Option<&'a str>,
+}
+
+#[derive(Deserialize)]
+struct TranslateResponse {
+    #[serde(rename = "translatedText")]
+    translated_text: String,
+}
+
+#[derive(Serialize)]
+struct DetectRequest<'a> {
+    q: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    api_key: Option<&'a str>,
+}
+
+#[derive(Deserialize)]
+struct DetectResult {
+    language: String,
+    // LibreTranslate reports 0-100
+    confidence: f64,
+}
+
+#[derive(Deserialize)]
+struct LanguageEntry {
+    code: String,
+    name: String,
+}
+
+pub struct LibreTranslateProvider {
+    pub(crate) http: Arc<HttpPool>,
+}
+
+#[async_trait]
+impl TranslationProvider for LibreTranslateProvider {
+    fn id(&self) -> &'static str {
+        "libretranslate"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "LibreTranslate"
+    }
+
+    // Self-hosted server: nothing to gate on, so the user switches it on explicitly
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            requires_api_key: false,
+            accepts_access_token: false,
+            requires_model: false,
+            enabled_by_default: false,
+            supports_model_selection: false,
+            supports_auto_detect: true,
+            is_llm: false,
+        }
+    }
+
+    fn default_base_url(&self) -> &'static str {
+        DEFAULT_BASE_URL
+    }
+
+    async fn translate(&self, request: &TranslationRequest) -> Result<TranslationOutcome, String> {
+        let client = self.http.client(10);
+        let url = format!("{}/translate", self.base_url(&request.settings));
+
+        let body = TranslateRequest {
+            q: &request.text,
+            source: &request.source_lang,
+            target: &request.target_lang,
+            format: "text",
+            api_key: request.settings.api_key(),
+        };
+
+        println!("Sending request to LibreTranslate ({})...", url);
+
+        let (response, attempts) = send_with_retry(SERVICE, client.post(&url).json(&body))
+            .await
+            .map_err(|e| map_request_error(SERVICE, e))?;
+
+        println!("Response status: {}", response.status());
+        if !response.status().is_success() {
+            return Err(status_error(SERVICE, response).await);
+        }
+
+        let parsed = response
+            .json::<TranslateResponse>()
+            .await
+            .map_err(|e| format!("Failed to parse LibreTranslate response: {}", e))?;
+
+        if parsed.translated_text.is_empty() {
+            return Err("Translation not found in LibreTranslate response".to_string());
+        }
+        if is_verbose() {
+            println!("Final translation: {}", parsed.translated_text);
+        }
+        Ok(self.outcome(parsed.translated_text, None).with_attempts(attempts))
+    }
+
+    async fn detect(&self, text: &str, settings: &ProviderSettings) -> Result<Vec<DetectedLanguage>, String> {
+        let client = self.http.client(8);
+        let url = format!("{}/detect", self.base_url(settings));
+
+        let (response, _) = send_with_retry(
+            SERVICE,
+            client
+                .post(&url)
+                .json(&DetectRequest { q: text, api_key: settings.api_key() }),
+        )
+        .await
+        .map_err(|e| map_request_error(SERVICE, e))?;
+
+        if !response.status().is_success() {
+            return Err(status_error(SERVICE, response).await);
+        }
+
+        let results = response
+            .json::<Vec<DetectResult>>()
+            .await
+            .map_err(|e| format!("Failed to parse LibreTranslate detection: {}", e))?;
+
+        Ok(results
+            .into_iter()
+            .map(|r| DetectedLanguage { language: r.language, confidence: r.confidence / 100.0 })
+            .collect())
+    }
+
+    async fn supported_languages(&self, settings: &ProviderSettings) -> Result<Vec<SupportedLanguage>, String> {
+        let client = self.http.client(8);
+        let url = format!("{}/languages", self.base_url(settings));
+
+        let (response, _) = send_with_retry(SERVICE, client.get(&url))
+            .await
+            .map_err(|e| map_request_error(SERVICE, e))?;
+
+        if !response.status().is_success() {
+            return Err(status_error(SERVICE, response).await);
+        }
+
+        let languages = response
+            .json::<Vec<LanguageEntry>>()
+            .await
+            .map_err(|e| format!("Failed to parse LibreTranslate languages: {}", e))?;
+
+        Ok(languages
+            .into_iter()
+            .map(|l| SupportedLanguage { code: l.code, name: l.name })
+            .collect())
+    }
+}
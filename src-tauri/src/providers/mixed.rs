@@ -0,0 +1,337 @@
+// Mixed-language text (Japanese prose with English identifiers, log lines, ...): only the
+// sentences that are not already in the target language are sent to the provider, and the rest
+// is kept verbatim in place. Text in one writing system goes to the provider as a whole, so LLMs
+// keep the context of the surrounding sentences.
+
+use std::ops::Range;
+
+use super::langdetect;
+use super::segment::split_sentences;
+use super::{
+    call_permit, CallError, ChunkSink, DetectedLanguage, TranslationOutcome, TranslationProvider, TranslationRequest,
+};
+
+// A segment in the target's script is translated only when detected as another language with at
+// least this confidence ("Das ist nicht gut." among English). Above the 0.6 that Latin text
+// without stopword evidence gets as an English guess, so identifiers and short snippets
+// ("npm install", "OK") count as the target's script and are kept.
+const KEEP_CONFIDENCE: f64 = 0.7;
+
+pub async fn translate_mixed(
+    provider: &dyn TranslationProvider,
+    request: &TranslationRequest,
+) -> Result<TranslationOutcome, CallError> {
+    let segmented = split_sentences(&request.text);
+    let target = request.target_lang.split('-').next().unwrap_or(&request.target_lang);
+    let detected: Vec<Option<DetectedLanguage>> = segmented
+        .chunks
+        .iter()
+        .map(|chunk| langdetect::detect(&chunk.text).into_iter().next())
+        .collect();
+    let keep: Vec<bool> = detected.iter().map(|best| is_kept(best.as_ref(), target)).collect();
+
+    // Only sentences in another script than the target's are split off ("German among English"
+    // is ordinary text for the provider, "English identifiers in Japanese" is not)
+    let other_script = |best: &Option<DetectedLanguage>| {
+        best.as_ref().is_some_and(|d| langdetect::script(&d.language) != langdetect::script(target))
+    };
+    let foreign_in_other_script = detected
+        .iter()
+        .zip(&keep)
+        .filter(|(_, kept)| !**kept)
+        .all(|(best, _)| other_script(best));
+
+    // Ordinary single-language text (or nothing to translate): the provider sees it as a whole
+    if keep.iter().all(|k| !k) || keep.iter().all(|k| *k) || !foreign_in_other_script {
+        return translate_one(provider, request).await;
+    }
+
+    let runs = foreign_runs(&keep);
+    println!(
+        "Mixed-language text: translating {} of {} segments in {} request(s)",
+        keep.iter().filter(|k| !**k).count(),
+        keep.len(),
+        runs.len()
+    );
+
+    let mut result = segmented.leading.clone();
+    let mut merged: Option<TranslationOutcome> = None;
+    let mut index = 0;
+    for run in runs {
+        let kept_from = result.len();
+        for chunk in &segmented.chunks[index..run.start] {
+            result.push_str(&chunk.text);
+            result.push_str(&chunk.separator);
+        }
+        if result.len() > kept_from {
+            request.emit_chunk(&result[kept_from..], &result);
+        }
+
+        // Consecutive foreign sentences go out together so the provider keeps their context
+        let run_chunks = &segmented.chunks[run.clone()];
+        let mut text = String::new();
+        for (i, chunk) in run_chunks.iter().enumerate() {
+            text.push_str(&chunk.text);
+            if i + 1 < run_chunks.len() {
+                text.push_str(&chunk.separator);
+            }
+        }
+        // Streamed output of the run is shown after the text assembled so far
+        let chunk_sink = request.chunk_sink.clone().map(|sink| {
+            let prefix = result.clone();
+            ChunkSink::new(move |delta, text| (sink.0)(delta, &format!("{}{}", prefix, text)))
+        });
+        let sub_request = TranslationRequest {
+            text,
+            chunk_sink,
+            ..request.clone()
+        };
+        let outcome = translate_one(provider, &sub_request).await?;
+        result.push_str(outcome.translated_text.trim_end());
+        result.push_str(&run_chunks[run_chunks.len() - 1].separator);
+
+        merged = Some(match merged {
+            None => outcome,
+            Some(mut merged) => {
+                merged.attempts = merged.attempts.max(outcome.attempts);
+                merged.segments.extend(outcome.segments);
+                merged
+            }
+        });
+        index = run.end;
+    }
+    for chunk in &segmented.chunks[index..] {
+        result.push_str(&chunk.text);
+        result.push_str(&chunk.separator);
+    }
+
+    // At least one run exists because not every segment is kept
+    let mut outcome = merged.ok_or_else(|| "Translation not found in response".to_string())?;
+    outcome.translated_text = result;
+    outcome.dictionary = None;
+    Ok(outcome)
+}
+
+// One provider request under its own rate-limit permit
+async fn translate_one(
+    provider: &dyn TranslationProvider,
+    request: &TranslationRequest,
+) -> Result<TranslationOutcome, CallError> {
+    let _permit = call_permit(provider, request).await?;
+    Ok(provider.translate(request).await?)
+}
+
+// Whether a segment stays as it is: segments in the target's script unless they are confidently
+// another language of that script. Segments without letters (numbers, symbols) are kept too.
+fn is_kept(best: Option<&DetectedLanguage>, target: &str) -> bool {
+    match best {
+        None => true,
+        Some(best) => {
+            langdetect::script(&best.language) == langdetect::script(target)
+                && (best.language == target || best.confidence < KEEP_CONFIDENCE)
+        }
+    }
+}
+
+// Index ranges of consecutive segments that need translating
+fn foreign_runs(keep: &[bool]) -> Vec<Range<usize>> {
+    let mut runs = Vec::new();
+    let mut start = None;
+    for (i, kept) in keep.iter().enumerate() {
+        match (kept, start) {
+            (false, None) => start = Some(i),
+            (true, Some(s)) => {
+                runs.push(s..i);
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        runs.push(s..keep.len());
+    }
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use async_trait::async_trait;
+
+    use super::*;
+    use crate::providers::{ProviderCapabilities, ProviderSettings};
+
+    // Answers "<text>" for every request, streaming it in two chunks, and records what it was sent
+    #[derive(Default)]
+    struct Recorder {
+        requests: Mutex<Vec<String>>,
+    }
+
+    impl Recorder {
+        fn requests(&self) -> Vec<String> {
+            self.requests.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl TranslationProvider for Recorder {
+        fn id(&self) -> &'static str {
+            "recorder"
+        }
+
+        fn display_name(&self) -> &'static str {
+            "Recorder"
+        }
+
+        fn capabilities(&self) -> ProviderCapabilities {
+            ProviderCapabilities {
+                requires_api_key: false,
+                accepts_access_token: false,
+                requires_model: false,
+                enabled_by_default: true,
+                supports_model_selection: false,
+                supports_auto_detect: true,
+                is_llm: true,
+            }
+        }
+
+        fn default_base_url(&self) -> &'static str {
+            "http://localhost"
+        }
+
+        async fn translate(&self, request: &TranslationRequest) -> Result<TranslationOutcome, String> {
+            self.requests.lock().unwrap().push(request.text.clone());
+            request.emit_chunk("<", "<");
+            let translated = format!("<{}>", request.text);
+            request.emit_chunk(&translated[1..], &translated);
+            Ok(TranslationOutcome {
+                provider_id: self.id().to_string(),
+                provider_name: self.display_name().to_string(),
+                translated_text: translated,
+                model_used: None,
+                detected_language: None,
+                attempts: 1,
+                fallback_errors: Vec::new(),
+                cached: false,
+                dictionary: None,
+                segments: Vec::new(),
+                latency_ms: 0,
+            })
+        }
+    }
+
+    fn request(text: &str, target: &str) -> TranslationRequest {
+        TranslationRequest {
+            text: text.to_string(),
+            source_lang: "auto".to_string(),
+            target_lang: target.to_string(),
+            settings: ProviderSettings::default(),
+            chunk_sink: None,
+            limiter: None,
+        }
+    }
+
+    async fn translate(text: &str, target: &str) -> (String, Vec<String>) {
+        let provider = Recorder::default();
+        let outcome = translate_mixed(&provider, &request(text, target)).await.unwrap();
+        (outcome.translated_text, provider.requests())
+    }
+
+    fn detected(language: &str, confidence: f64) -> DetectedLanguage {
+        DetectedLanguage { language: language.to_string(), confidence }
+    }
+
+    #[test]
+    fn foreign_runs_group_consecutive_segments() {
+        assert_eq!(foreign_runs(&[]), Vec::<Range<usize>>::new());
+        assert_eq!(foreign_runs(&[true, true]), Vec::<Range<usize>>::new());
+        assert_eq!(foreign_runs(&[false, false]), vec![0..2]);
+        assert_eq!(foreign_runs(&[true, false, false, true, false]), vec![1..3, 4..5]);
+        assert_eq!(foreign_runs(&[false, true, true, false, false]), vec![0..1, 3..5]);
+    }
+
+    #[test]
+    fn is_kept_by_script() {
+        // (detected language, confidence, target, kept)
+        let cases = [
+            ("en", 0.9, "en", true),
+            // English guesses without evidence (identifiers) are still Latin text for an English target
+            ("en", 0.6, "en", true),
+            ("en", 0.6, "fr", true),
+            ("ja", 0.95, "en", false),
+            ("en", 0.9, "ja", false),
+            ("zh", 0.47, "ja", true),
+            ("zh", 0.86, "ja", false),
+            ("de", 0.9, "en", false),
+            ("uk", 0.9, "ru", false),
+        ];
+        for (language, confidence, target, kept) in cases {
+            let best = detected(language, confidence);
+            assert_eq!(is_kept(Some(&best), target), kept, "{} {} -> {}", language, confidence, target);
+        }
+        assert!(is_kept(None, "en"));
+    }
+
+    #[tokio::test]
+    async fn japanese_into_english_keeps_the_english_lines() {
+        let text = "エラーが出ました。\nnpm install\nThis is the output of the build.\nもう一度試します。";
+        let (translated, requests) = translate(text, "en").await;
+        assert_eq!(requests, ["エラーが出ました。", "もう一度試します。"]);
+        assert_eq!(
+            translated,
+            "<エラーが出ました。>\nnpm install\nThis is the output of the build.\n<もう一度試します。>"
+        );
+    }
+
+    #[tokio::test]
+    async fn english_into_japanese_sends_consecutive_sentences_together() {
+        let text = "Run the build first. Then check the output.\n\nビルドが終わりました。\nIt is done.";
+        let (translated, requests) = translate(text, "ja").await;
+        assert_eq!(requests, ["Run the build first. Then check the output.", "It is done."]);
+        assert_eq!(translated, "<Run the build first. Then check the output.>\n\nビルドが終わりました。\n<It is done.>");
+    }
+
+    #[tokio::test]
+    async fn single_script_text_goes_out_whole() {
+        // Everything already in the target language
+        let text = "This is a pen.\nAnd that is the book.";
+        assert_eq!(translate(text, "en").await.1, [text]);
+        // Nothing in the target language
+        let text = "これはペンです。\nあれは本です。";
+        assert_eq!(translate(text, "en").await.1, [text]);
+        // Another language in the target's script is ordinary text for the provider
+        let text = "Das ist nicht gut und ich bin müde.\nThis is the book.\nこれは本です。";
+        assert_eq!(translate(text, "en").await.1, [text]);
+    }
+
+    #[tokio::test]
+    async fn separators_are_kept_byte_for_byte() {
+        let text = "  これはペンです。\r\n\t  This is the pen and it is mine.   \n\n\n次です。  \n";
+        let (translated, requests) = translate(text, "en").await;
+        assert_eq!(requests, ["これはペンです。", "次です。"]);
+        assert_eq!(translated, "  <これはペンです。>\r\n\t  This is the pen and it is mine.   \n\n\n<次です。>  \n");
+    }
+
+    #[tokio::test]
+    async fn streamed_chunks_follow_the_assembled_text() {
+        let chunks = Arc::new(Mutex::new(Vec::new()));
+        let sink_chunks = chunks.clone();
+        let mut request = request("これはペンです。\nThis is the pen and it is mine.\n次です。", "en");
+        request.chunk_sink = Some(ChunkSink::new(move |_, text| sink_chunks.lock().unwrap().push(text.to_string())));
+
+        let outcome = translate_mixed(&Recorder::default(), &request).await.unwrap();
+        let chunks = chunks.lock().unwrap().clone();
+        assert_eq!(
+            chunks,
+            [
+                "<",
+                "<これはペンです。>",
+                "<これはペンです。>\nThis is the pen and it is mine.\n",
+                "<これはペンです。>\nThis is the pen and it is mine.\n<",
+                "<これはペンです。>\nThis is the pen and it is mine.\n<次です。>",
+            ]
+        );
+        assert_eq!(outcome.translated_text, chunks[chunks.len() - 1]);
+    }
+}
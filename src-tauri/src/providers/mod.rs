@@ -0,0 +1,459 @@
+// Translation providers: every backend (Google web, Gemini, ...) implements
+// `TranslationProvider` and is registered in `ProviderRegistry`, so the
+// frontend only needs a provider id to request a translation.
+
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::Arc;
+
+use self::http::{HttpPool, TimedClient};
+use self::rate_limit::{RateLimited, RateLimiter, RatePermit};
+
+pub mod anthropic;
+pub mod azure;
+pub mod cache;
+pub mod deepl;
+pub mod fallback;
+pub mod gemini;
+pub mod google;
+pub mod google_cloud;
+pub mod http;
+pub mod langdetect;
+pub mod libretranslate;
+pub mod mixed;
+#[cfg(feature = "offline-nmt")]
+pub mod offline;
+pub mod ollama;
+pub mod openai;
+mod prompt;
+pub mod rate_limit;
+mod retry;
+pub mod routing;
+mod segment;
+
+pub(crate) use retry::send_with_retry;
+
+// Per-call provider settings sent from the frontend (persisted in localStorage)
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ProviderSettings {
+    #[serde(default)]
+    pub api_key: Option<String>,
+    // OAuth access token, for providers with `accepts_access_token`
+    #[serde(default)]
+    pub access_token: Option<String>,
+    #[serde(default)]
+    pub model: Option<String>,
+    // Overrides the provider's default endpoint (e.g. corporate reverse proxy, local mock server)
+    #[serde(default)]
+    pub base_url: Option<String>,
+    // Provider-specific options (e.g. DeepL formality), parsed by each provider
+    #[serde(default)]
+    pub options: serde_json::Map<String, serde_json::Value>,
+}
+
+impl ProviderSettings {
+    // Non-empty API key, if any
+    pub fn api_key(&self) -> Option<&str> {
+        self.api_key.as_deref().map(str::trim).filter(|k| !k.is_empty())
+    }
+
+    // Non-empty access token, if any
+    pub fn access_token(&self) -> Option<&str> {
+        self.access_token.as_deref().map(str::trim).filter(|t| !t.is_empty())
+    }
+
+    // Non-empty model name, if any ("auto" is resolved by the provider)
+    pub fn model(&self) -> Option<&str> {
+        self.model
+            .as_deref()
+            .map(str::trim)
+            .filter(|m| !m.is_empty() && *m != "auto")
+    }
+
+    // Non-empty base URL override, if any
+    pub fn base_url(&self) -> Option<&str> {
+        self.base_url.as_deref().map(str::trim).filter(|u| !u.is_empty())
+    }
+
+    // Typed view of `options`; invalid or missing options fall back to defaults
+    pub fn options<T: DeserializeOwned + Default>(&self) -> T {
+        serde_json::from_value(serde_json::Value::Object(self.options.clone())).unwrap_or_else(|e| {
+            println!("Ignoring invalid provider options: {}", e);
+            T::default()
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TranslationRequest {
+    pub text: String,
+    // ISO 639-1 code, or "auto" when the source language is unknown
+    pub source_lang: String,
+    // ISO 639-1 code (e.g. "ja", "en")
+    pub target_lang: String,
+    pub settings: ProviderSettings,
+    // Receives partial output from providers that stream (None when nobody is listening)
+    pub chunk_sink: Option<ChunkSink>,
+    // Client-side rate limit of the provider (None: unlimited)
+    pub limiter: Option<Arc<RateLimiter>>,
+}
+
+impl TranslationRequest {
+    // Permission to send one HTTP request for this call
+    pub async fn acquire_permit(&self, provider_id: &str) -> Result<Option<RatePermit>, RateLimited> {
+        match &self.limiter {
+            Some(limiter) => limiter.acquire(provider_id).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    // Forward a partial translation to the listener, if any
+    pub fn emit_chunk(&self, delta: &str, text: &str) {
+        if let Some(sink) = &self.chunk_sink {
+            (sink.0)(delta, text);
+        }
+    }
+}
+
+// Rate-limit permit for a whole call to `provider`; providers that limit their own requests
+// take their permits while translating
+pub async fn call_permit(
+    provider: &dyn TranslationProvider,
+    request: &TranslationRequest,
+) -> Result<Option<RatePermit>, RateLimited> {
+    if provider.limits_own_requests() {
+        return Ok(None);
+    }
+    request.acquire_permit(provider.id()).await
+}
+
+// Callback for streamed output: (newly received delta, full text so far)
+type ChunkFn = dyn Fn(&str, &str) + Send + Sync;
+
+#[derive(Clone)]
+pub struct ChunkSink(Arc<ChunkFn>);
+
+impl ChunkSink {
+    pub fn new(f: impl Fn(&str, &str) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(f))
+    }
+}
+
+impl fmt::Debug for ChunkSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ChunkSink")
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranslationOutcome {
+    pub provider_id: String,
+    pub provider_name: String,
+    pub translated_text: String,
+    pub model_used: Option<String>,
+    // Source language reported by the provider itself, when it has built-in detection
+    pub detected_language: Option<DetectedLanguage>,
+    // HTTP attempts the translation request took (1 = no retries)
+    pub attempts: u32,
+    // Providers of a fallback chain that failed before this one answered
+    pub fallback_errors: Vec<ProviderFailure>,
+    // Served from the translation cache without contacting the provider
+    #[serde(default)]
+    pub cached: bool,
+    // Dictionary data for single words / short phrases (providers with a dictionary only)
+    #[serde(default)]
+    pub dictionary: Option<DictionaryEntry>,
+    // Source / translation pairs per sentence, for providers that report them
+    #[serde(default)]
+    pub segments: Vec<TranslatedSegment>,
+    // Wall-clock time of the command, including retries and fallbacks (set by the command layer)
+    #[serde(default)]
+    pub latency_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranslatedSegment {
+    pub source: String,
+    pub translation: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DictionaryEntry {
+    // Headword as looked up (the base form when the provider reports one, e.g. "run" for "ran")
+    pub word: String,
+    // Romanization of the source / translation (romaji, pinyin, ...)
+    pub source_transliteration: Option<String>,
+    pub transliteration: Option<String>,
+    // Alternative translations grouped by part of speech
+    pub senses: Vec<DictionarySense>,
+    // Synonyms of the headword (source language), grouped by part of speech
+    pub synonyms: Vec<DictionarySense>,
+    pub examples: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DictionarySense {
+    pub part_of_speech: String,
+    // Most common first
+    pub terms: Vec<DictionaryTerm>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DictionaryTerm {
+    pub text: String,
+    // Source-language words this term translates back to
+    pub reverse_translations: Vec<String>,
+    // Relative frequency, 0.0 - 1.0
+    pub score: Option<f64>,
+}
+
+// Failure of a provider call made under the rate limiter
+#[derive(Debug)]
+pub enum CallError {
+    // Refused locally; nothing was sent
+    RateLimited(RateLimited),
+    Failed(String),
+}
+
+impl From<String> for CallError {
+    fn from(message: String) -> Self {
+        CallError::Failed(message)
+    }
+}
+
+impl From<RateLimited> for CallError {
+    fn from(e: RateLimited) -> Self {
+        CallError::RateLimited(e)
+    }
+}
+
+impl From<CallError> for String {
+    fn from(e: CallError) -> Self {
+        match e {
+            CallError::RateLimited(limited) => limited.into(),
+            CallError::Failed(message) => message,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderFailure {
+    pub provider_id: String,
+    pub provider_name: String,
+    pub error: String,
+}
+
+impl TranslationOutcome {
+    pub fn with_attempts(mut self, attempts: u32) -> Self {
+        self.attempts = attempts;
+        self
+    }
+
+    pub fn with_latency(mut self, started: std::time::Instant) -> Self {
+        self.latency_ms = started.elapsed().as_millis() as u64;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct ProviderCapabilities {
+    pub requires_api_key: bool,
+    // An OAuth access token (`ProviderSettings::access_token`) can stand in for the API key
+    pub accepts_access_token: bool,
+    // No usable default model; the user has to pick one
+    pub requires_model: bool,
+    // False for providers that must be switched on in settings (e.g. self-hosted servers)
+    pub enabled_by_default: bool,
+    pub supports_model_selection: bool,
+    pub supports_auto_detect: bool,
+    pub is_llm: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetectedLanguage {
+    pub language: String,
+    // 0.0 - 1.0
+    pub confidence: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SupportedLanguage {
+    pub code: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderInfo {
+    pub id: String,
+    pub display_name: String,
+    pub capabilities: ProviderCapabilities,
+    pub default_base_url: String,
+    pub base_url_env: String,
+}
+
+#[async_trait]
+pub trait TranslationProvider: Send + Sync {
+    fn id(&self) -> &'static str;
+    fn display_name(&self) -> &'static str;
+    fn capabilities(&self) -> ProviderCapabilities;
+    fn default_base_url(&self) -> &'static str;
+
+    async fn translate(&self, request: &TranslationRequest) -> Result<TranslationOutcome, String>;
+
+    // Providers that split a call into several HTTP requests take a rate-limit permit for each
+    // of them (`TranslationRequest::acquire_permit`); the others are limited once per call
+    fn limits_own_requests(&self) -> bool {
+        false
+    }
+
+    // Models selectable in settings (only for providers with `supports_model_selection`)
+    async fn list_models(&self, _settings: &ProviderSettings) -> Result<Vec<String>, String> {
+        Err(format!("{} does not support model listing", self.display_name()))
+    }
+
+    // Ranked language guesses for `text`, most likely first
+    async fn detect(&self, _text: &str, _settings: &ProviderSettings) -> Result<Vec<DetectedLanguage>, String> {
+        Err(format!("{} does not support language detection", self.display_name()))
+    }
+
+    async fn supported_languages(&self, _settings: &ProviderSettings) -> Result<Vec<SupportedLanguage>, String> {
+        Err(format!("{} does not support listing languages", self.display_name()))
+    }
+
+    fn info(&self) -> ProviderInfo {
+        ProviderInfo {
+            id: self.id().to_string(),
+            display_name: self.display_name().to_string(),
+            capabilities: self.capabilities(),
+            default_base_url: self.default_base_url().to_string(),
+            base_url_env: base_url_env(self.id()),
+        }
+    }
+
+    // Endpoint used for every request of this provider
+    fn base_url(&self, settings: &ProviderSettings) -> String {
+        resolve_base_url(self.id(), settings.base_url(), self.default_base_url())
+    }
+
+    // Helper for implementations: wrap translated text into an outcome tagged with this provider
+    fn outcome(&self, translated_text: String, model_used: Option<String>) -> TranslationOutcome {
+        TranslationOutcome {
+            provider_id: self.id().to_string(),
+            provider_name: self.display_name().to_string(),
+            translated_text,
+            model_used,
+            detected_language: None,
+            attempts: 1,
+            fallback_errors: Vec::new(),
+            cached: false,
+            dictionary: None,
+            segments: Vec::new(),
+            latency_ms: 0,
+        }
+    }
+}
+
+// Ordered set of providers; the order is the display order in the popup
+pub struct ProviderRegistry {
+    providers: Vec<Arc<dyn TranslationProvider>>,
+}
+
+impl ProviderRegistry {
+    pub fn new() -> Self {
+        Self { providers: Vec::new() }
+    }
+
+    // Registry with every built-in provider (LLMs are listed above Google, then DeepL, ...),
+    // all sharing one pooled HTTP client
+    pub fn with_defaults(http: Arc<HttpPool>) -> Self {
+        let mut registry = Self::new();
+        registry.register(Arc::new(gemini::GeminiProvider { http: http.clone() }));
+        registry.register(Arc::new(anthropic::AnthropicProvider { http: http.clone() }));
+        registry.register(Arc::new(google::GoogleWebProvider { http: http.clone() }));
+        registry.register(Arc::new(deepl::DeeplProvider { http: http.clone() }));
+        registry.register(Arc::new(openai::OpenAiCompatibleProvider { http: http.clone() }));
+        registry.register(Arc::new(ollama::OllamaProvider { http: http.clone() }));
+        registry.register(Arc::new(libretranslate::LibreTranslateProvider { http: http.clone() }));
+        registry.register(Arc::new(azure::AzureTranslatorProvider { http: http.clone() }));
+        registry.register(Arc::new(google_cloud::GoogleCloudProvider { http }));
+        registry
+    }
+
+    // Register a provider, replacing any existing provider with the same id
+    pub fn register(&mut self, provider: Arc<dyn TranslationProvider>) {
+        if let Some(existing) = self.providers.iter_mut().find(|p| p.id() == provider.id()) {
+            *existing = provider;
+        } else {
+            self.providers.push(provider);
+        }
+    }
+
+    pub fn get(&self, id: &str) -> Option<Arc<dyn TranslationProvider>> {
+        self.providers.iter().find(|p| p.id() == id).cloned()
+    }
+
+    pub fn list(&self) -> Vec<ProviderInfo> {
+        self.providers.iter().map(|p| p.info()).collect()
+    }
+}
+
+impl Default for ProviderRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Environment variable overriding a provider's base URL, e.g. AFTERPOT_GEMINI_BASE_URL
+pub(crate) fn base_url_env(provider_id: &str) -> String {
+    format!("AFTERPOT_{}_BASE_URL", provider_id.to_uppercase().replace('-', "_"))
+}
+
+// Resolve a provider's base URL: environment override > settings > built-in default
+pub(crate) fn resolve_base_url(provider_id: &str, configured: Option<&str>, default: &str) -> String {
+    let from_env = std::env::var(base_url_env(provider_id))
+        .ok()
+        .filter(|u| !u.trim().is_empty());
+    let url = from_env.as_deref().or(configured).unwrap_or(default);
+    url.trim().trim_end_matches('/').to_string()
+}
+
+// Shared mapping of transport errors to user-facing messages
+pub(crate) fn map_request_error(service: &str, e: reqwest::Error) -> String {
+    println!("Request error ({}): {}", service, e);
+    if e.is_timeout() {
+        format!("{}へのリクエストがタイムアウトしました。インターネット接続を確認してください。", service)
+    } else if e.is_connect() {
+        format!("{}に接続できませんでした。オフラインの可能性があります。", service)
+    } else {
+        format!("{}へのリクエストに失敗しました: {}", service, e)
+    }
+}
+
+// Shared mapping of non-success HTTP responses (body only shown in verbose mode)
+pub(crate) async fn status_error(service: &str, response: reqwest::Response) -> String {
+    let status = response.status();
+    let err = match response.text().await {
+        Ok(body) if crate::is_verbose() => {
+            format!("{} API request failed with status {}: {}", service, status, body)
+        }
+        _ => format!("{} API request failed with status {}", service, status),
+    };
+    println!("Error: {}", err);
+    err
+}
+
+// English language name for an ISO code, used in LLM prompts
+pub(crate) fn language_name(code: &str) -> &str {
+    match code {
+        "ja" => "Japanese",
+        "en" => "English",
+        "zh" => "Chinese",
+        "ko" => "Korean",
+        "fr" => "French",
+        "de" => "German",
+        "es" => "Spanish",
+        other => other,
+    }
+}
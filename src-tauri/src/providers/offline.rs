@@ -0,0 +1,266 @@
+// Offline neural machine translation: Marian (opus-mt) models running on the CPU via candle.
+//
+// Each installed language pair lives in `<app data>/models/<source>-<target>/`:
+//   config.json            Hugging Face Marian config
+//   model.safetensors      weights (f16 or f32; converted to f32 on load)
+//   source.tokenizer.json  source-side tokenizer (converted from source.spm)
+//   target.tokenizer.json  target-side tokenizer (converted from target.spm)
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use async_trait::async_trait;
+use candle_core::{DType, Device, Tensor};
+use candle_nn::VarBuilder;
+use candle_transformers::models::marian;
+use tokenizers::Tokenizer;
+
+use super::{
+    langdetect, ProviderCapabilities, ProviderSettings, TranslationOutcome, TranslationProvider,
+    TranslationRequest,
+};
+use crate::is_verbose;
+
+// Upper bound on generated tokens per line (Marian models are trained on sentence-length input)
+const MAX_OUTPUT_TOKENS: usize = 512;
+
+struct MarianTranslator {
+    model: marian::MTModel,
+    config: marian::Config,
+    source_tokenizer: Tokenizer,
+    target_tokenizer: Tokenizer,
+    device: Device,
+}
+
+impl MarianTranslator {
+    fn load(dir: &Path) -> Result<Self, String> {
+        println!("Loading offline model from {:?}", dir);
+        let config_json = std::fs::read_to_string(dir.join("config.json"))
+            .map_err(|e| format!("Failed to read model config: {}", e))?;
+        let config: marian::Config = serde_json::from_str(&config_json)
+            .map_err(|e| format!("Unsupported model config: {}", e))?;
+
+        let device = Device::Cpu;
+        let weights = std::fs::read(dir.join("model.safetensors"))
+            .map_err(|e| format!("Failed to read model weights: {}", e))?;
+        let vb = VarBuilder::from_buffered_safetensors(weights, DType::F32, &device)
+            .map_err(|e| format!("Failed to load model weights: {}", e))?;
+        let model = marian::MTModel::new(&config, vb)
+            .map_err(|e| format!("Failed to build model: {}", e))?;
+
+        let source_tokenizer = Tokenizer::from_file(dir.join("source.tokenizer.json"))
+            .map_err(|e| format!("Failed to load source tokenizer: {}", e))?;
+        let target_tokenizer = Tokenizer::from_file(dir.join("target.tokenizer.json"))
+            .map_err(|e| format!("Failed to load target tokenizer: {}", e))?;
+
+        Ok(Self { model, config, source_tokenizer, target_tokenizer, device })
+    }
+
+    // Greedy decoding of a single line
+    fn translate_line(&mut self, text: &str) -> Result<String, String> {
+        let err = |e: candle_core::Error| format!("Offline inference failed: {}", e);
+
+        let mut input_ids = self
+            .source_tokenizer
+            .encode(text, true)
+            .map_err(|e| format!("Failed to tokenize input: {}", e))?
+            .get_ids()
+            .to_vec();
+        input_ids.push(self.config.eos_token_id);
+
+        self.model.reset_kv_cache();
+        let input = Tensor::new(input_ids.as_slice(), &self.device)
+            .and_then(|t| t.unsqueeze(0))
+            .map_err(err)?;
+        let encoder_xs = self.model.encoder().forward(&input, 0).map_err(err)?;
+
+        let mut token_ids = vec![self.config.decoder_start_token_id];
+        for index in 0..MAX_OUTPUT_TOKENS {
+            // The KV cache holds earlier positions, so only the newest token is fed after the first step
+            let context_size = if index >= 1 { 1 } else { token_ids.len() };
+            let start_pos = token_ids.len().saturating_sub(context_size);
+            let next_token = Tensor::new(&token_ids[start_pos..], &self.device)
+                .and_then(|t| t.unsqueeze(0))
+                .and_then(|ids| self.model.decode(&ids, &encoder_xs, start_pos))
+                .and_then(|logits| logits.squeeze(0))
+                .and_then(|logits| logits.get(logits.dim(0)? - 1))
+                .and_then(|logits| logits.argmax(0))
+                .and_then(|token| token.to_scalar::<u32>())
+                .map_err(err)?;
+            if next_token == self.config.eos_token_id || next_token == self.config.forced_eos_token_id {
+                break;
+            }
+            token_ids.push(next_token);
+        }
+
+        self.target_tokenizer
+            .decode(&token_ids[1..], true)
+            .map_err(|e| format!("Failed to decode output: {}", e))
+    }
+}
+
+fn scan_pairs(models_dir: &Path) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(models_dir) else {
+        return Vec::new();
+    };
+    let mut pairs: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().join("model.safetensors").is_file())
+        .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+        .filter(|name| name.split_once('-').is_some())
+        .collect();
+    pairs.sort();
+    pairs
+}
+
+type LoadedModels = HashMap<String, Arc<Mutex<MarianTranslator>>>;
+
+pub struct OfflineProvider {
+    models_dir: PathBuf,
+    // Loaded models keyed by language pair ("en-ja"); loading takes seconds, so keep them around
+    loaded: Mutex<LoadedModels>,
+}
+
+impl OfflineProvider {
+    pub fn new(models_dir: PathBuf) -> Self {
+        Self { models_dir, loaded: Mutex::new(HashMap::new()) }
+    }
+
+    // Installed language pairs, e.g. ["en-ja", "ja-en"] (the directory scan runs on the blocking pool)
+    async fn installed_pairs(&self) -> Result<Vec<String>, String> {
+        let models_dir = self.models_dir.clone();
+        tokio::task::spawn_blocking(move || scan_pairs(&models_dir))
+            .await
+            .map_err(|e| format!("Offline model scan failed: {}", e))
+    }
+
+    // Pick the pair for this request. An unknown source is detected locally first; when that is
+    // not confident, the pair chosen in settings (or the only pair into the target) is used
+    async fn resolve_pair(&self, request: &TranslationRequest) -> Result<String, String> {
+        let pairs = self.installed_pairs().await?;
+        let target_lang = &request.target_lang;
+        let detected = match request.source_lang.as_str() {
+            "auto" => langdetect::detect(&request.text)
+                .into_iter()
+                .next()
+                .filter(|best| best.confidence >= langdetect::CONFIDENT)
+                .map(|best| best.language),
+            source => Some(source.to_string()),
+        };
+        if let Some(source_lang) = detected {
+            let pair = format!("{}-{}", source_lang, target_lang);
+            return if pairs.contains(&pair) {
+                Ok(pair)
+            } else {
+                Err(format!("オフラインモデル {} がインストールされていません", pair))
+            };
+        }
+
+        let suffix = format!("-{}", target_lang);
+        let chosen = request.settings.model().filter(|m| m.ends_with(&suffix));
+        if let Some(pair) = chosen.filter(|m| pairs.iter().any(|p| p == m)) {
+            return Ok(pair.to_string());
+        }
+        let mut candidates = pairs.into_iter().filter(|p| p.ends_with(&suffix));
+        match (candidates.next(), candidates.next()) {
+            (Some(pair), None) => Ok(pair),
+            (None, _) => Err(format!("{}へのオフラインモデルがインストールされていません", target_lang)),
+            (Some(_), Some(_)) => Err("元言語を特定できないためオフライン翻訳できません".to_string()),
+        }
+    }
+
+    async fn translator(&self, pair: &str) -> Result<Arc<Mutex<MarianTranslator>>, String> {
+        if let Some(translator) = self.lock_loaded()?.get(pair) {
+            return Ok(translator.clone());
+        }
+
+        // Reading the weights and building the model takes seconds: run it on the blocking pool,
+        // without holding the cache lock (other pairs stay usable meanwhile)
+        let dir = self.models_dir.join(pair);
+        let translator = tokio::task::spawn_blocking(move || MarianTranslator::load(&dir))
+            .await
+            .map_err(|e| format!("Offline model loading task failed: {}", e))??;
+
+        // A concurrent request may have loaded the same pair in the meantime; keep the first one
+        let mut loaded = self.lock_loaded()?;
+        Ok(loaded.entry(pair.to_string()).or_insert_with(|| Arc::new(Mutex::new(translator))).clone())
+    }
+
+    fn lock_loaded(&self) -> Result<MutexGuard<'_, LoadedModels>, String> {
+        self.loaded
+            .lock()
+            .map_err(|e| format!("Failed to lock offline model cache: {}", e))
+    }
+}
+
+#[async_trait]
+impl TranslationProvider for OfflineProvider {
+    fn id(&self) -> &'static str {
+        "offline"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "オフライン"
+    }
+
+    // Opt-in: models have to be installed first
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            requires_api_key: false,
+            accepts_access_token: false,
+            requires_model: false,
+            enabled_by_default: false,
+            // The "model" is the pair used when the source language cannot be detected
+            supports_model_selection: true,
+            supports_auto_detect: false,
+            is_llm: false,
+        }
+    }
+
+    // No network endpoint; the models directory is shown instead
+    fn default_base_url(&self) -> &'static str {
+        ""
+    }
+
+    async fn translate(&self, request: &TranslationRequest) -> Result<TranslationOutcome, String> {
+        let pair = self.resolve_pair(request).await?;
+        let translator = self.translator(&pair).await?;
+        let text = request.text.clone();
+
+        println!("Translating offline with model {}", pair);
+
+        // CPU-bound inference must not block the async runtime
+        let translated = tokio::task::spawn_blocking(move || -> Result<String, String> {
+            let mut translator = translator
+                .lock()
+                .map_err(|e| format!("Failed to lock offline model: {}", e))?;
+            // Line by line keeps inputs sentence-sized and preserves the original layout
+            text.split('\n')
+                .map(|line| {
+                    if line.trim().is_empty() {
+                        Ok(line.to_string())
+                    } else {
+                        translator.translate_line(line)
+                    }
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .map(|lines| lines.join("\n"))
+        })
+        .await
+        .map_err(|e| format!("Offline translation task failed: {}", e))??;
+
+        if translated.trim().is_empty() {
+            return Err("オフライン翻訳の結果が空でした".to_string());
+        }
+        if is_verbose() {
+            println!("Final translation: {}", translated);
+        }
+        Ok(self.outcome(translated, Some(pair)))
+    }
+
+    // Installed language pairs double as the selectable "models"
+    async fn list_models(&self, _settings: &ProviderSettings) -> Result<Vec<String>, String> {
+        self.installed_pairs().await
+    }
+}
@@ -0,0 +1,223 @@
+// Ollama native API (/api/chat, /api/tags) for fully on-device translation
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    language_name, prompt, status_error, HttpPool, ProviderCapabilities,
+    ProviderSettings, TranslationOutcome, TranslationProvider, TranslationRequest,
+};
+use crate::is_verbose;
+
+const SERVICE: &str = "Ollama";
+const DEFAULT_BASE_URL: &str = "http://localhost:11434";
+
+#[derive(Serialize)]
+struct ChatRequest<'a> {
+    model: &'a str,
+    messages: Vec<ChatMessage<'a>>,
+    stream: bool,
+    options: ChatOptions,
+}
+
+#[derive(Serialize)]
+struct ChatMessage<'a> {
+    role: &'a str,
+    content: &'a str,
+}
+
+#[derive(Serialize)]
+struct ChatOptions {
+    temperature: f32,
+}
+
+// One line of the NDJSON stream returned by /api/chat
+#[derive(Deserialize)]
+struct ChatChunk {
+    #[serde(default)]
+    message: Option<ChunkMessage>,
+    #[serde(default)]
+    done: bool,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ChunkMessage {
+    #[serde(default)]
+    content: String,
+}
+
+#[derive(Deserialize)]
+struct TagsResponse {
+    #[serde(default)]
+    models: Vec<TagModel>,
+}
+
+#[derive(Deserialize)]
+struct TagModel {
+    name: String,
+}
+
+pub struct OllamaProvider {
+    pub(crate) http: Arc<HttpPool>,
+}
+
+#[async_trait]
+impl TranslationProvider for OllamaProvider {
+    fn id(&self) -> &'static str {
+        "ollama"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Ollama"
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            requires_api_key: false,
+            accepts_access_token: false,
+            requires_model: true,
+            enabled_by_default: true,
+            supports_model_selection: true,
+            supports_auto_detect: true,
+            is_llm: true,
+        }
+    }
+
+    fn default_base_url(&self) -> &'static str {
+        DEFAULT_BASE_URL
+    }
+
+    async fn translate(&self, request: &TranslationRequest) -> Result<TranslationOutcome, String> {
+        let model = request
+            .settings
+            .model()
+            .ok_or_else(|| "Ollamaのモデルが設定されていません".to_string())?;
+        let base_url = self.base_url(&request.settings);
+
+        // CPU inference can take a while; the stream keeps the connection busy meanwhile
+        let client = self.http.client(120);
+        let url = format!("{}/api/chat", base_url);
+
+        let system_instruction = prompt::system_instruction(language_name(&request.target_lang));
+        let body = ChatRequest {
+            model,
+            messages: vec![
+                ChatMessage { role: "system", content: &system_instruction },
+                ChatMessage { role: "user", content: &request.text },
+            ],
+            stream: true,
+            options: ChatOptions { temperature: 0.0 },
+        };
+
+        println!("Sending Ollama chat request (model: {})", model);
+
+        // Sent once: a refused connection to the local server is not going to recover within the
+        // retry backoff, and the "not running" message should show right away
+        let mut response = client
+            .post(&url)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| map_ollama_error(&base_url, e))?;
+
+        println!("Response status: {}", response.status());
+        if !response.status().is_success() {
+            return Err(status_error(SERVICE, response).await);
+        }
+
+        // NDJSON: one JSON object per line, possibly split across network chunks
+        let mut buffer: Vec<u8> = Vec::new();
+        let mut translated = String::new();
+        let mut done = false;
+        while !done {
+            let chunk = response
+                .chunk()
+                .await
+                .map_err(|e| map_ollama_error(&base_url, e))?;
+            let Some(chunk) = chunk else { break };
+            buffer.extend_from_slice(&chunk);
+
+            while let Some(pos) = buffer.iter().position(|b| *b == b'\n') {
+                let line: Vec<u8> = buffer.drain(..=pos).collect();
+                done |= apply_chunk_line(&line, &mut translated, request)?;
+            }
+        }
+        // Last line may not be newline-terminated
+        if !buffer.is_empty() {
+            apply_chunk_line(&buffer, &mut translated, request)?;
+        }
+
+        let translated = translated.trim().to_string();
+        if translated.is_empty() {
+            return Err("Translation not found in Ollama response".to_string());
+        }
+        if is_verbose() {
+            println!("Final translation: {}", translated);
+        }
+        Ok(self.outcome(translated, Some(model.to_string())))
+    }
+
+    async fn list_models(&self, settings: &ProviderSettings) -> Result<Vec<String>, String> {
+        let base_url = self.base_url(settings);
+        let client = self.http.client(5);
+
+        let response = client
+            .get(format!("{}/api/tags", base_url))
+            .send()
+            .await
+            .map_err(|e| map_ollama_error(&base_url, e))?;
+
+        if !response.status().is_success() {
+            return Err(status_error(SERVICE, response).await);
+        }
+
+        let tags = response
+            .json::<TagsResponse>()
+            .await
+            .map_err(|e| format!("Failed to parse Ollama model list: {}", e))?;
+
+        let models: Vec<String> = tags.models.into_iter().map(|m| m.name).collect();
+        println!("Found {} local Ollama models", models.len());
+        Ok(models)
+    }
+}
+
+// Append one NDJSON line to the translation and forward it as a chunk; returns true once
+// Ollama reports completion
+fn apply_chunk_line(line: &[u8], translated: &mut String, request: &TranslationRequest) -> Result<bool, String> {
+    let line = String::from_utf8_lossy(line);
+    let line = line.trim();
+    if line.is_empty() {
+        return Ok(false);
+    }
+
+    let chunk = serde_json::from_str::<ChatChunk>(line)
+        .map_err(|e| format!("Failed to parse Ollama stream: {}", e))?;
+    if let Some(error) = chunk.error {
+        return Err(format!("Ollamaがエラーを返しました: {}", error));
+    }
+    if let Some(message) = chunk.message.filter(|message| !message.content.is_empty()) {
+        translated.push_str(&message.content);
+        request.emit_chunk(&message.content, translated);
+    }
+    Ok(chunk.done)
+}
+
+// Connection refused means the local server is not running, which deserves a clear message
+fn map_ollama_error(base_url: &str, e: reqwest::Error) -> String {
+    println!("Request error ({}): {}", SERVICE, e);
+    if e.is_connect() {
+        format!(
+            "Ollamaサーバーが起動していません（{}）。`ollama serve` を実行してから再試行してください。",
+            base_url
+        )
+    } else if e.is_timeout() {
+        "Ollamaの応答がタイムアウトしました。より小さいモデルを試してください。".to_string()
+    } else {
+        format!("Ollamaへのリクエストに失敗しました: {}", e)
+    }
+}
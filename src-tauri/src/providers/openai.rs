@@ -0,0 +1,151 @@
+// OpenAI-compatible Chat Completions API (OpenAI, OpenRouter, llama.cpp, vLLM, LM Studio, ...)
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    language_name, map_request_error, prompt, send_with_retry, status_error, HttpPool,
+    ProviderCapabilities, TranslationOutcome, TranslationProvider, TranslationRequest,
+};
+use crate::{is_verbose, truncate_chars};
+
+const SERVICE: &str = "OpenAI互換API";
+const DEFAULT_BASE_URL: &str = "https://api.openai.com";
+
+#[derive(Serialize)]
+struct ChatRequest<'a> {
+    model: &'a str,
+    messages: Vec<ChatMessage<'a>>,
+    temperature: f32,
+    stream: bool,
+}
+
+#[derive(Serialize)]
+struct ChatMessage<'a> {
+    role: &'a str,
+    content: &'a str,
+}
+
+#[derive(Deserialize)]
+struct ChatResponse {
+    #[serde(default)]
+    model: Option<String>,
+    choices: Vec<ChatChoice>,
+}
+
+#[derive(Deserialize)]
+struct ChatChoice {
+    message: ChatResponseMessage,
+}
+
+#[derive(Deserialize)]
+struct ChatResponseMessage {
+    #[serde(default)]
+    content: Option<String>,
+}
+
+pub struct OpenAiCompatibleProvider {
+    pub(crate) http: Arc<HttpPool>,
+}
+
+#[async_trait]
+impl TranslationProvider for OpenAiCompatibleProvider {
+    fn id(&self) -> &'static str {
+        "openai"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "OpenAI互換"
+    }
+
+    // API key is optional so that self-hosted servers work without one
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            requires_api_key: false,
+            accepts_access_token: false,
+            requires_model: true,
+            enabled_by_default: true,
+            supports_model_selection: true,
+            supports_auto_detect: true,
+            is_llm: true,
+        }
+    }
+
+    fn default_base_url(&self) -> &'static str {
+        DEFAULT_BASE_URL
+    }
+
+    async fn translate(&self, request: &TranslationRequest) -> Result<TranslationOutcome, String> {
+        let model = request
+            .settings
+            .model()
+            .ok_or_else(|| "OpenAI互換プロバイダーのモデルが設定されていません".to_string())?;
+
+        // Local servers can be slow on CPU, so allow more time than Gemini
+        let client = self.http.client(30);
+        let url = chat_completions_url(&self.base_url(&request.settings));
+
+        let system_instruction = prompt::system_instruction(language_name(&request.target_lang));
+        let body = ChatRequest {
+            model,
+            messages: vec![
+                ChatMessage { role: "system", content: &system_instruction },
+                ChatMessage { role: "user", content: &request.text },
+            ],
+            temperature: 0.0,
+            stream: false,
+        };
+
+        println!("Sending chat completion request to {} (model: {})", url, model);
+
+        let mut builder = client.post(&url).json(&body);
+        if let Some(api_key) = request.settings.api_key() {
+            builder = builder.bearer_auth(api_key);
+        }
+
+        let (response, attempts) = send_with_retry(SERVICE, builder)
+            .await
+            .map_err(|e| map_request_error(SERVICE, e))?;
+
+        println!("Response status: {}", response.status());
+        if !response.status().is_success() {
+            return Err(status_error("OpenAI-compatible", response).await);
+        }
+
+        let body = response
+            .text()
+            .await
+            .map_err(|e| format!("Failed to read response body: {}", e))?;
+
+        if is_verbose() {
+            println!("Response body (first 300 chars): {}", truncate_chars(&body, 300));
+        }
+
+        let parsed = serde_json::from_str::<ChatResponse>(&body)
+            .map_err(|e| format!("Failed to parse chat completion response: {}", e))?;
+
+        let translated = parsed
+            .choices
+            .into_iter()
+            .next()
+            .and_then(|c| c.message.content)
+            .map(|c| c.trim().to_string())
+            .filter(|c| !c.is_empty())
+            .ok_or_else(|| "Translation not found in chat completion response".to_string())?;
+
+        Ok(self
+            .outcome(translated, Some(parsed.model.unwrap_or_else(|| model.to_string())))
+            .with_attempts(attempts))
+    }
+}
+
+// Accept base URLs with or without the "/v1" suffix (LM Studio documents "http://localhost:1234/v1")
+fn chat_completions_url(base_url: &str) -> String {
+    if base_url.ends_with("/v1") {
+        format!("{}/chat/completions", base_url)
+    } else {
+        format!("{}/v1/chat/completions", base_url)
+    }
+}
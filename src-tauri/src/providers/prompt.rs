@@ -0,0 +1,21 @@
+// Translation prompt shared by the LLM providers (Gemini, OpenAI-compatible, ...)
+
+// Part of the translation cache key; bump whenever the prompt wording changes
+pub(crate) const PROMPT_VERSION: u32 = 2;
+
+// System-style instruction to improve translation quality and preserve formatting/placeholders
+pub(crate) fn system_instruction(target_language: &str) -> String {
+    format!(
+        concat!(
+            "You are a professional translation engine. Translate the user-provided text into {} only.\n",
+            "Constraints:\n",
+            "- Preserve original formatting, line breaks, markdown, code blocks, and list structure.\n",
+            "- Keep placeholders and variables untouched (e.g., {{like_this}}, {{{{curly}}}}, %s, %d, {{{{name}}}}, <tag>, URLs, and file paths).\n",
+            "- Do not add explanations or commentary. Output only the translated text.\n",
+            "- Maintain numbers, units, punctuation, emojis, and inline symbols.\n",
+            "- If the text is mostly code or untranslatable terms, keep them as-is and translate surrounding prose naturally.\n",
+            "- Prefer concise, natural, context-appropriate wording.\n"
+        ),
+        target_language
+    )
+}
@@ -0,0 +1,221 @@
+// Client-side rate limiting per provider: a token bucket (requests per minute) plus a cap on
+// concurrent requests. Requests over the limit wait in line or are rejected without being sent,
+// depending on the provider's `on_limit` setting.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::is_verbose;
+
+// Queued requests give up after this long instead of sitting behind a long backlog
+const MAX_QUEUE_WAIT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OnLimit {
+    #[default]
+    Queue,
+    Reject,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(default)]
+pub struct RateLimitConfig {
+    // 0 = unlimited
+    pub requests_per_minute: u32,
+    // 0 = unlimited
+    pub max_concurrent: u32,
+    pub on_limit: OnLimit,
+}
+
+impl RateLimitConfig {
+    // The undocumented Google web endpoint starts answering with 429s / CAPTCHA pages when hammered
+    fn default_for(provider_id: &str) -> Self {
+        match provider_id {
+            "google" => Self {
+                requests_per_minute: 30,
+                max_concurrent: 2,
+                on_limit: OnLimit::Queue,
+            },
+            _ => Self::default(),
+        }
+    }
+}
+
+// A request that was not sent because of the local limit
+#[derive(Debug, Clone, Serialize)]
+pub struct RateLimited {
+    pub provider_id: String,
+    // When a token frees up; None when waiting on concurrent requests
+    pub retry_after_ms: Option<u64>,
+}
+
+impl fmt::Display for RateLimited {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.retry_after_ms {
+            Some(ms) => write!(
+                f,
+                "{}へのリクエストが多すぎるため送信を控えました（約{}秒後に再試行できます）",
+                self.provider_id,
+                ms.div_ceil(1000)
+            ),
+            None => write!(f, "{}への同時リクエストが上限に達したため送信を控えました", self.provider_id),
+        }
+    }
+}
+
+impl From<RateLimited> for String {
+    fn from(e: RateLimited) -> Self {
+        e.to_string()
+    }
+}
+
+struct TokenBucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+struct Limit {
+    config: RateLimitConfig,
+    bucket: Mutex<TokenBucket>,
+    concurrency: Option<Arc<Semaphore>>,
+}
+
+impl Limit {
+    fn new(config: RateLimitConfig) -> Self {
+        Self {
+            config,
+            bucket: Mutex::new(TokenBucket {
+                tokens: burst_capacity(&config),
+                refilled_at: Instant::now(),
+            }),
+            concurrency: (config.max_concurrent > 0)
+                .then(|| Arc::new(Semaphore::new(config.max_concurrent as usize))),
+        }
+    }
+
+    // Take a token, or report how long until one is available
+    fn try_take(&self) -> Result<(), Duration> {
+        if self.config.requests_per_minute == 0 {
+            return Ok(());
+        }
+        let per_sec = self.config.requests_per_minute as f64 / 60.0;
+        let mut bucket = match self.bucket.lock() {
+            Ok(bucket) => bucket,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let now = Instant::now();
+        let refill = now.duration_since(bucket.refilled_at).as_secs_f64() * per_sec;
+        bucket.tokens = (bucket.tokens + refill).min(burst_capacity(&self.config));
+        bucket.refilled_at = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / per_sec))
+        }
+    }
+}
+
+// Bursts are capped at a tenth of the per-minute budget so a backlog is not fired all at once
+fn burst_capacity(config: &RateLimitConfig) -> f64 {
+    (config.requests_per_minute as f64 / 10.0).max(1.0)
+}
+
+// Held for the duration of a request; frees the concurrency slot when dropped
+pub struct RatePermit {
+    _slot: Option<OwnedSemaphorePermit>,
+}
+
+#[derive(Default)]
+pub struct RateLimiter {
+    // User overrides; providers without one use `RateLimitConfig::default_for`
+    configs: Mutex<HashMap<String, RateLimitConfig>>,
+    limits: Mutex<HashMap<String, Arc<Limit>>>,
+}
+
+impl fmt::Debug for RateLimiter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("RateLimiter")
+    }
+}
+
+impl RateLimiter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Replace the per-provider limits; buckets restart full
+    pub fn configure(&self, configs: HashMap<String, RateLimitConfig>) {
+        println!("Rate limits configured for {} provider(s)", configs.len());
+        if let Ok(mut current) = self.configs.lock() {
+            *current = configs;
+        }
+        if let Ok(mut limits) = self.limits.lock() {
+            limits.clear();
+        }
+    }
+
+    fn limit_for(&self, provider_id: &str) -> Arc<Limit> {
+        let config = self
+            .configs
+            .lock()
+            .ok()
+            .and_then(|configs| configs.get(provider_id).copied())
+            .unwrap_or_else(|| RateLimitConfig::default_for(provider_id));
+        match self.limits.lock() {
+            Ok(mut limits) => limits
+                .entry(provider_id.to_string())
+                .or_insert_with(|| Arc::new(Limit::new(config)))
+                .clone(),
+            Err(_) => Arc::new(Limit::new(config)),
+        }
+    }
+
+    // Wait for (or fail to get) permission to send one request to `provider_id`
+    pub async fn acquire(&self, provider_id: &str) -> Result<RatePermit, RateLimited> {
+        let limit = self.limit_for(provider_id);
+        let deadline = Instant::now() + MAX_QUEUE_WAIT;
+        let limited = |retry_after: Option<Duration>| RateLimited {
+            provider_id: provider_id.to_string(),
+            retry_after_ms: retry_after.map(|d| d.as_millis() as u64),
+        };
+
+        // Concurrency slot first, so queued requests do not use up tokens while they wait
+        let slot = match &limit.concurrency {
+            None => None,
+            Some(semaphore) => {
+                let semaphore = semaphore.clone();
+                let permit = match limit.config.on_limit {
+                    OnLimit::Reject => semaphore.try_acquire_owned().ok(),
+                    OnLimit::Queue => tokio::time::timeout(MAX_QUEUE_WAIT, semaphore.acquire_owned())
+                        .await
+                        .ok()
+                        .and_then(Result::ok),
+                };
+                Some(permit.ok_or_else(|| limited(None))?)
+            }
+        };
+
+        loop {
+            let wait = match limit.try_take() {
+                Ok(()) => return Ok(RatePermit { _slot: slot }),
+                Err(wait) => wait,
+            };
+            if limit.config.on_limit == OnLimit::Reject || Instant::now() + wait > deadline {
+                println!("{} rate limited locally (next token in {:?})", provider_id, wait);
+                return Err(limited(Some(wait)));
+            }
+            if is_verbose() {
+                println!("{} rate limited locally, queued for {:?}", provider_id, wait);
+            }
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
@@ -0,0 +1,184 @@
+// Shared retry policy for outbound HTTP calls: transient failures (timeouts, connection
+// errors, 429, 5xx) are retried with jittered exponential backoff, honoring `Retry-After`.
+// Other 4xx responses (bad key, bad request) are returned immediately.
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::{Duration, SystemTime};
+
+use reqwest::{RequestBuilder, Response, StatusCode};
+
+#[derive(Debug, Clone, Copy)]
+struct RetryPolicy {
+    // Total tries including the first request
+    max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    // A longer `Retry-After` is not waited out; the error is returned instead
+    max_retry_after: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(8),
+            max_retry_after: Duration::from_secs(20),
+        }
+    }
+}
+
+impl RetryPolicy {
+    // Exponential backoff with "full jitter": a random delay in [0, base * 2^(attempt-1)]
+    fn backoff(&self, attempt: u32) -> Duration {
+        self.backoff_ceiling(attempt).mul_f64(jitter())
+    }
+
+    fn backoff_ceiling(&self, attempt: u32) -> Duration {
+        let exp = self.base_delay.saturating_mul(1u32 << (attempt.max(1) - 1).min(16));
+        exp.min(self.max_delay)
+    }
+}
+
+// Send `builder` under the default policy; returns the final response and how many attempts it took.
+// Non-success responses are still returned (callers turn them into errors as before).
+pub(crate) async fn send_with_retry(
+    service: &str,
+    builder: RequestBuilder,
+) -> Result<(Response, u32), reqwest::Error> {
+    let policy = RetryPolicy::default();
+    let mut attempt = 1;
+    loop {
+        // Streaming bodies cannot be cloned; such requests are only sent once
+        let Some(retry_builder) = builder.try_clone().filter(|_| attempt < policy.max_attempts) else {
+            return builder.send().await.map(|response| (response, attempt));
+        };
+
+        let delay = match retry_builder.send().await {
+            Ok(response) if !is_retryable_status(response.status()) => return Ok((response, attempt)),
+            Ok(response) => {
+                let status = response.status();
+                match retry_after(&response) {
+                    Some(wait) if wait > policy.max_retry_after => {
+                        println!("{} asked to retry after {:?}; giving up", service, wait);
+                        return Ok((response, attempt));
+                    }
+                    Some(wait) => {
+                        println!("{} returned {} (attempt {}), retrying after {:?}", service, status, attempt, wait);
+                        wait
+                    }
+                    None => {
+                        let wait = policy.backoff(attempt);
+                        println!("{} returned {} (attempt {}), retrying in {:?}", service, status, attempt, wait);
+                        wait
+                    }
+                }
+            }
+            Err(e) if is_retryable_error(&e) => {
+                let wait = policy.backoff(attempt);
+                println!("{} request failed (attempt {}): {}; retrying in {:?}", service, attempt, e, wait);
+                wait
+            }
+            Err(e) => return Err(e),
+        };
+
+        tokio::time::sleep(delay).await;
+        attempt += 1;
+    }
+}
+
+fn is_retryable_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS
+        || status == StatusCode::REQUEST_TIMEOUT
+        || status.is_server_error()
+}
+
+// Only failures to reach the server; other request errors (building the request, encoding the
+// body, ...) would fail the same way again
+fn is_retryable_error(e: &reqwest::Error) -> bool {
+    e.is_timeout() || e.is_connect()
+}
+
+fn retry_after(response: &Response) -> Option<Duration> {
+    let value = response.headers().get(reqwest::header::RETRY_AFTER)?.to_str().ok()?;
+    parse_retry_after(value, SystemTime::now())
+}
+
+// `Retry-After` as delay-seconds ("120") or as an HTTP date; a date in the past means now
+fn parse_retry_after(value: &str, now: SystemTime) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<f64>() {
+        return Some(secs)
+            .filter(|secs| secs.is_finite() && *secs >= 0.0)
+            .map(Duration::from_secs_f64);
+    }
+    let date = httpdate::parse_http_date(value).ok()?;
+    Some(date.duration_since(now).unwrap_or_default())
+}
+
+// Random fraction in [0, 1) without pulling in a RNG crate (RandomState is randomly seeded)
+fn jitter() -> f64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u128(
+        SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or_default(),
+    );
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_ceiling_doubles_up_to_the_cap() {
+        let policy = RetryPolicy::default();
+        let ceilings: Vec<u128> = (1..=6)
+            .map(|attempt| policy.backoff_ceiling(attempt).as_millis())
+            .collect();
+        assert_eq!(ceilings, [500, 1000, 2000, 4000, 8000, 8000]);
+        assert_eq!(policy.backoff_ceiling(40), policy.max_delay);
+    }
+
+    #[test]
+    fn backoff_stays_within_the_ceiling() {
+        let policy = RetryPolicy::default();
+        for attempt in 1..=5 {
+            for _ in 0..200 {
+                assert!(policy.backoff(attempt) <= policy.backoff_ceiling(attempt));
+            }
+        }
+    }
+
+    #[test]
+    fn jitter_is_a_fraction() {
+        let samples: Vec<f64> = (0..1000).map(|_| jitter()).collect();
+        assert!(samples.iter().all(|j| (0.0..1.0).contains(j)));
+        // Not stuck on one value
+        assert!(samples.iter().any(|j| (j - samples[0]).abs() > f64::EPSILON));
+    }
+
+    #[test]
+    fn parses_retry_after_seconds() {
+        let now = SystemTime::now();
+        assert_eq!(parse_retry_after("120", now), Some(Duration::from_secs(120)));
+        assert_eq!(parse_retry_after(" 1.5 ", now), Some(Duration::from_millis(1500)));
+        assert_eq!(parse_retry_after("0", now), Some(Duration::ZERO));
+        assert_eq!(parse_retry_after("-3", now), None);
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+
+    #[test]
+    fn parses_retry_after_http_dates() {
+        let now = httpdate::parse_http_date("Sun, 06 Nov 1994 08:49:37 GMT").unwrap();
+        assert_eq!(
+            parse_retry_after("Sun, 06 Nov 1994 08:50:07 GMT", now),
+            Some(Duration::from_secs(30))
+        );
+        // Already passed: retry right away
+        assert_eq!(parse_retry_after("Sun, 06 Nov 1994 08:00:00 GMT", now), Some(Duration::ZERO));
+    }
+}
@@ -0,0 +1,230 @@
+// Translation direction rules: given the detected source language, pick the target language
+// (and optionally the providers to use for that pair). Every entry point (popup, hotkey, tray)
+// goes through `resolve`, so "ja → en, everything else → ja" behaves the same everywhere.
+
+use serde::{Deserialize, Serialize};
+
+// Source pattern matching any detected language (including an undetected one)
+pub const ANY_LANGUAGE: &str = "*";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DirectionRule {
+    // Detected language code, or "*"
+    pub source: String,
+    pub target: String,
+    // Preferred provider ids for this pair, in order; empty means every enabled provider
+    #[serde(default)]
+    pub providers: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoutingRules {
+    // Target when no rule matches
+    pub default_target: String,
+    // Target used instead when the text is already in the chosen target language
+    pub reverse_target: String,
+    #[serde(default = "default_true")]
+    pub reverse_when_same: bool,
+    #[serde(default)]
+    pub rules: Vec<DirectionRule>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for RoutingRules {
+    // The popup's historical behaviour: Japanese → English, everything else → Japanese
+    fn default() -> Self {
+        Self {
+            default_target: "ja".to_string(),
+            reverse_target: "en".to_string(),
+            reverse_when_same: true,
+            rules: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ResolvedDirection {
+    // "auto" when detection failed
+    pub source_lang: String,
+    pub target_lang: String,
+    // Detected language code, None when detection failed
+    pub detected_language: Option<String>,
+    pub providers: Vec<String>,
+    // Index of the rule that decided the target (None: default target)
+    pub rule_index: Option<usize>,
+    // The target was swapped because the text was already in it
+    pub reversed: bool,
+    // The detected language is only a local guess (set by the command layer)
+    pub low_confidence: bool,
+}
+
+impl RoutingRules {
+    // Most specific rule for `detected`: an exact source match beats "*", earlier rules win ties
+    fn matching_rule(&self, detected: Option<&str>) -> Option<(usize, &DirectionRule)> {
+        let exact = detected.and_then(|language| {
+            self.rules.iter().enumerate().find(|(_, rule)| rule.source.eq_ignore_ascii_case(language))
+        });
+        exact.or_else(|| self.rules.iter().enumerate().find(|(_, rule)| rule.source == ANY_LANGUAGE))
+    }
+
+    // Providers preferred for the final pair: the deciding rule's, or those of a rule written
+    // for exactly this pair (so "en → ja: deepl" also applies when en was reached by reversal)
+    fn providers_for(&self, source: Option<&str>, target: &str, deciding: Option<&DirectionRule>) -> Vec<String> {
+        if let Some(rule) = deciding.filter(|rule| rule.target == target && !rule.providers.is_empty()) {
+            return rule.providers.clone();
+        }
+        let matches = |rule: &&DirectionRule| {
+            rule.target == target
+                && !rule.providers.is_empty()
+                && source.is_some_and(|language| rule.source.eq_ignore_ascii_case(language))
+        };
+        self.rules.iter().find(matches).map(|rule| rule.providers.clone()).unwrap_or_default()
+    }
+
+    pub fn resolve(&self, detected: Option<&str>) -> ResolvedDirection {
+        let detected = detected.filter(|language| !language.is_empty() && *language != "unknown" && *language != "auto");
+        let rule = self.matching_rule(detected);
+        let mut target_lang = match rule {
+            Some((_, rule)) => rule.target.clone(),
+            None => self.default_target.clone(),
+        };
+
+        let mut reversed = false;
+        if self.reverse_when_same && detected.is_some_and(|language| language.eq_ignore_ascii_case(&target_lang)) {
+            // A rule/default pointing back at the source falls through to the other language
+            target_lang = if target_lang.eq_ignore_ascii_case(&self.default_target) {
+                self.reverse_target.clone()
+            } else {
+                self.default_target.clone()
+            };
+            reversed = true;
+        }
+
+        ResolvedDirection {
+            source_lang: detected.unwrap_or("auto").to_string(),
+            providers: self.providers_for(detected, &target_lang, rule.map(|(_, rule)| rule)),
+            detected_language: detected.map(str::to_string),
+            target_lang,
+            rule_index: rule.map(|(index, _)| index),
+            reversed,
+            low_confidence: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(source: &str, target: &str, providers: &[&str]) -> DirectionRule {
+        DirectionRule {
+            source: source.to_string(),
+            target: target.to_string(),
+            providers: providers.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+
+    fn rules(rules: Vec<DirectionRule>) -> RoutingRules {
+        RoutingRules { rules, ..RoutingRules::default() }
+    }
+
+    #[test]
+    fn exact_rules_beat_wildcards_and_earlier_rules_win() {
+        let routing = rules(vec![
+            rule("*", "ko", &[]),
+            rule("zh", "en", &["deepl"]),
+            rule("EN", "ja", &["google"]),
+            rule("zh", "fr", &["openai"]),
+            rule("*", "de", &[]),
+        ]);
+        // (detected, target, rule index, providers)
+        let cases = [
+            (Some("zh"), "en", Some(1), vec!["deepl"]),
+            (Some("en"), "ja", Some(2), vec!["google"]),
+            (Some("ru"), "ko", Some(0), vec![]),
+            (None, "ko", Some(0), vec![]),
+            (Some("unknown"), "ko", Some(0), vec![]),
+            (Some("auto"), "ko", Some(0), vec![]),
+        ];
+        for (detected, target, rule_index, providers) in cases {
+            let resolved = routing.resolve(detected);
+            assert_eq!(resolved.target_lang, target, "{:?}", detected);
+            assert_eq!(resolved.rule_index, rule_index, "{:?}", detected);
+            assert_eq!(resolved.providers, providers, "{:?}", detected);
+            assert!(!resolved.reversed, "{:?}", detected);
+        }
+        assert_eq!(routing.resolve(None).source_lang, "auto");
+        assert_eq!(routing.resolve(Some("zh")).source_lang, "zh");
+    }
+
+    #[test]
+    fn default_target_without_rules() {
+        let routing = RoutingRules::default();
+        // (detected, target, reversed)
+        let cases = [
+            (Some("en"), "ja", false),
+            (Some("fr"), "ja", false),
+            (None, "ja", false),
+            (Some("ja"), "en", true),
+            (Some("JA"), "en", true),
+        ];
+        for (detected, target, reversed) in cases {
+            let resolved = routing.resolve(detected);
+            assert_eq!((resolved.target_lang.as_str(), resolved.reversed), (target, reversed), "{:?}", detected);
+            assert_eq!(resolved.rule_index, None);
+        }
+    }
+
+    #[test]
+    fn text_already_in_the_target_is_reversed() {
+        let routing = rules(vec![rule("*", "ko", &[]), rule("fr", "fr", &["deepl"])]);
+        // (detected, target, reversed): a rule pointing at the source falls back to the default
+        // target, and the default target itself to the reverse target
+        let cases = [(Some("ko"), "ja", true), (Some("fr"), "ja", true), (Some("en"), "ko", false)];
+        for (detected, target, reversed) in cases {
+            let resolved = routing.resolve(detected);
+            assert_eq!((resolved.target_lang.as_str(), resolved.reversed), (target, reversed), "{:?}", detected);
+        }
+        // The deciding rule's providers were meant for another pair
+        assert!(routing.resolve(Some("fr")).providers.is_empty());
+
+        let routing = RoutingRules { reverse_when_same: false, ..routing };
+        let resolved = routing.resolve(Some("ko"));
+        assert_eq!((resolved.target_lang.as_str(), resolved.reversed), ("ko", false));
+    }
+
+    #[test]
+    fn providers_for_the_final_pair() {
+        let routing = rules(vec![
+            rule("*", "ja", &["google"]),
+            rule("JA", "en", &[]),
+            rule("ja", "en", &["deepl"]),
+            rule("en", "ja", &["openai"]),
+        ]);
+        let wildcard = &routing.rules[0];
+        // (source, target, deciding rule, providers)
+        let cases = [
+            // The deciding rule's providers when its target is the final one
+            (Some("en"), "ja", Some(wildcard), vec!["google"]),
+            (None, "ja", Some(wildcard), vec!["google"]),
+            // Reached another target (reversal): a rule written for exactly that pair, skipping
+            // rules without providers
+            (Some("ja"), "en", Some(wildcard), vec!["deepl"]),
+            (Some("ja"), "en", None, vec!["deepl"]),
+            // No rule for the pair, or no source to match it by
+            (Some("ja"), "ko", Some(wildcard), vec![]),
+            (None, "en", None, vec![]),
+        ];
+        for (source, target, deciding, providers) in cases {
+            assert_eq!(routing.providers_for(source, target, deciding), providers, "{:?} -> {}", source, target);
+        }
+
+        // Through resolve: "*" → ja decides for ja text, which is reversed to en
+        let resolved = routing.resolve(Some("ja"));
+        assert_eq!(resolved.target_lang, "en");
+        assert_eq!(resolved.providers, ["deepl"]);
+    }
+}
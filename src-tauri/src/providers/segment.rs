@@ -0,0 +1,243 @@
+// Splitting long text into translation-sized chunks at paragraph / sentence boundaries,
+// keeping the whitespace between chunks so translations can be put back in the original layout
+
+// Sentence terminators that end a sentence on their own (no following space in CJK text)
+const CJK_TERMINATORS: [char; 5] = ['。', '！', '？', '；', '…'];
+// Terminators that end a sentence only when followed by whitespace ("e.g." / "3.14" stay intact)
+const LATIN_TERMINATORS: [char; 3] = ['.', '!', '?'];
+// Closing quotes/brackets that belong to the sentence they follow
+const CLOSERS: [char; 9] = ['」', '』', '）', '】', '"', '\'', ')', '”', '’'];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Chunk {
+    pub text: String,
+    // Whitespace (spaces, line breaks) that followed this chunk; re-inserted after its translation
+    pub separator: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Segmented {
+    // Whitespace before the first chunk
+    pub leading: String,
+    pub chunks: Vec<Chunk>,
+}
+
+impl Segmented {
+    // Join per-chunk translations (same order as `chunks`) with the original separators
+    pub fn reassemble<S: AsRef<str>>(&self, translations: &[S]) -> String {
+        let mut out = self.leading.clone();
+        for (chunk, translation) in self.chunks.iter().zip(translations) {
+            out.push_str(translation.as_ref());
+            out.push_str(&chunk.separator);
+        }
+        out
+    }
+}
+
+// Split `text` into chunks of at most `max_chars` characters. Whole lines are packed together
+// first; a line that is too long is split into sentences, and a sentence that is still too long
+// is split at the last space (or hard at `max_chars` for unspaced text).
+pub(crate) fn split_for_translation(text: &str, max_chars: usize) -> Segmented {
+    let max_chars = max_chars.max(1);
+    let pieces = pieces(text, |line| {
+        if line.chars().count() <= max_chars {
+            return vec![Chunk { text: line.to_string(), separator: String::new() }];
+        }
+        sentences(line)
+            .into_iter()
+            .flat_map(|sentence| wrap(sentence, max_chars))
+            .collect()
+    });
+    pack(pieces, max_chars)
+}
+
+// Every sentence of every line as its own chunk (nothing is packed together)
+pub(crate) fn split_sentences(text: &str) -> Segmented {
+    pack(pieces(text, sentences), 0)
+}
+
+// Join consecutive pieces while they fit in `max_chars`; empty pieces only widen the gaps
+fn pack(pieces: Vec<Chunk>, max_chars: usize) -> Segmented {
+    let mut segmented = Segmented::default();
+    let mut current: Option<Chunk> = None;
+    let mut current_len = 0;
+
+    for piece in pieces {
+        if piece.text.is_empty() {
+            // Blank line: widen the gap after the previous chunk
+            match current.as_mut() {
+                Some(chunk) => chunk.separator.push_str(&piece.separator),
+                None => segmented.leading.push_str(&piece.separator),
+            }
+            continue;
+        }
+
+        let piece_len = piece.text.chars().count();
+        match current.as_mut() {
+            Some(chunk) if current_len + chunk.separator.chars().count() + piece_len <= max_chars => {
+                current_len += chunk.separator.chars().count() + piece_len;
+                chunk.text.push_str(&std::mem::take(&mut chunk.separator));
+                chunk.text.push_str(&piece.text);
+                chunk.separator = piece.separator;
+            }
+            _ => {
+                segmented.chunks.extend(current.take());
+                current_len = piece_len;
+                current = Some(piece);
+            }
+        }
+    }
+    segmented.chunks.extend(current);
+    segmented
+}
+
+// Parts of each line as produced by `split_line`, with the line's trailing whitespace
+// appended to its last part
+fn pieces(text: &str, split_line: impl Fn(&str) -> Vec<Chunk>) -> Vec<Chunk> {
+    let mut pieces = Vec::new();
+    for line in text.split_inclusive('\n') {
+        let content = line.trim_end();
+        let line_end = &line[content.len()..];
+        let body = content.trim_start();
+        if !content.is_empty() && body.len() < content.len() {
+            // Indentation is kept with the gap before the line
+            pieces.push(Chunk { text: String::new(), separator: content[..content.len() - body.len()].to_string() });
+        }
+
+        let mut parts = split_line(body);
+        match parts.last_mut() {
+            Some(last) => last.separator.push_str(line_end),
+            None => parts.push(Chunk { text: String::new(), separator: line_end.to_string() }),
+        }
+        pieces.extend(parts);
+    }
+    pieces
+}
+
+// Sentences of a single line, each with the whitespace that follows it
+fn sentences(line: &str) -> Vec<Chunk> {
+    let mut out = Vec::new();
+    let mut start = 0;
+    let mut chars = line.char_indices().peekable();
+
+    while let Some((_, c)) = chars.next() {
+        let is_cjk = CJK_TERMINATORS.contains(&c);
+        if !is_cjk && !LATIN_TERMINATORS.contains(&c) {
+            continue;
+        }
+        // Runs like "?!" or "。」" stay with the sentence
+        while let Some(&(_, next)) = chars.peek() {
+            if CJK_TERMINATORS.contains(&next) || LATIN_TERMINATORS.contains(&next) || CLOSERS.contains(&next) {
+                chars.next();
+            } else {
+                break;
+            }
+        }
+        let end = chars.peek().map(|&(i, _)| i).unwrap_or(line.len());
+        let followed_by_space = chars.peek().is_some_and(|&(_, next)| next.is_whitespace());
+        if !is_cjk && !followed_by_space && end < line.len() {
+            continue;
+        }
+
+        let mut gap_end = end;
+        while let Some(&(i, next)) = chars.peek() {
+            if !next.is_whitespace() {
+                break;
+            }
+            chars.next();
+            gap_end = i + next.len_utf8();
+        }
+        out.push(Chunk { text: line[start..end].to_string(), separator: line[end..gap_end].to_string() });
+        start = gap_end;
+    }
+
+    if start < line.len() {
+        out.push(Chunk { text: line[start..].to_string(), separator: String::new() });
+    }
+    out
+}
+
+// Break an over-long sentence at spaces (or anywhere, for unspaced scripts)
+fn wrap(sentence: Chunk, max_chars: usize) -> Vec<Chunk> {
+    if sentence.text.chars().count() <= max_chars {
+        return vec![sentence];
+    }
+
+    let mut out = Vec::new();
+    let mut rest = sentence.text.as_str();
+    while rest.chars().count() > max_chars {
+        let limit = rest.char_indices().nth(max_chars).map(|(i, _)| i).unwrap_or(rest.len());
+        let (text, gap_end) = match rest[..limit].rfind(char::is_whitespace) {
+            Some(space) if space > 0 => {
+                let gap_end = space + rest[space..].len() - rest[space..].trim_start().len();
+                (&rest[..space], gap_end)
+            }
+            _ => (&rest[..limit], limit),
+        };
+        out.push(Chunk { text: text.to_string(), separator: rest[text.len()..gap_end].to_string() });
+        rest = &rest[gap_end..];
+    }
+    out.push(Chunk { text: rest.to_string(), separator: sentence.separator });
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Translating every chunk to itself must give back the input byte for byte
+    fn assert_round_trip(text: &str, max_chars: usize) {
+        for segmented in [split_for_translation(text, max_chars), split_sentences(text)] {
+            let identity: Vec<&str> = segmented.chunks.iter().map(|chunk| chunk.text.as_str()).collect();
+            assert_eq!(segmented.reassemble(&identity), text);
+            assert!(segmented.chunks.iter().all(|chunk| !chunk.text.is_empty()));
+        }
+    }
+
+    #[test]
+    fn round_trips_line_breaks_and_indentation() {
+        assert_round_trip("First line.\r\nSecond line.\r\n\r\n  Indented line.\r\n", 15);
+        assert_round_trip("\n\n  leading blank lines\n\t\ttabbed\n   \ntrailing spaces   ", 10);
+        assert_round_trip("fn main() {\n    println!(\"hi\");\n}\n", 12);
+    }
+
+    #[test]
+    fn round_trips_abbreviations_and_decimals() {
+        let text = "Use a tool, e.g. a hammer. Pi is 3.14 or so! Really?! Yes.";
+        assert_round_trip(text, 20);
+        let sentences: Vec<String> = split_sentences(text).chunks.into_iter().map(|chunk| chunk.text).collect();
+        assert_eq!(sentences, ["Use a tool, e.g.", "a hammer.", "Pi is 3.14 or so!", "Really?!", "Yes."]);
+    }
+
+    #[test]
+    fn round_trips_cjk_sentences() {
+        let text = "これはペンです。「本当？」はい！\n次の行；続き…";
+        assert_round_trip(text, 5);
+        assert_eq!(split_sentences(text).chunks[1].text, "「本当？」");
+    }
+
+    #[test]
+    fn splits_long_unspaced_text_hard() {
+        let text = "あ".repeat(25);
+        let segmented = split_for_translation(&text, 10);
+        assert_eq!(segmented.chunks.len(), 3);
+        assert!(segmented.chunks.iter().all(|chunk| chunk.text.chars().count() <= 10));
+        assert_round_trip(&text, 10);
+        assert_round_trip("word ".repeat(30).trim_end(), 12);
+    }
+
+    #[test]
+    fn packs_short_lines_within_the_limit() {
+        let segmented = split_for_translation("one\ntwo\nthree\n", 8);
+        let texts: Vec<&str> = segmented.chunks.iter().map(|chunk| chunk.text.as_str()).collect();
+        assert_eq!(texts, ["one\ntwo", "three"]);
+        assert_eq!(segmented.chunks[1].separator, "\n");
+    }
+
+    #[test]
+    fn whitespace_only_text_has_no_chunks() {
+        let segmented = split_for_translation(" \r\n\t\n", 100);
+        assert!(segmented.chunks.is_empty());
+        assert_eq!(segmented.reassemble::<&str>(&[]), " \r\n\t\n");
+    }
+}
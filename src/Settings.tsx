@@ -3,6 +3,7 @@ import { appWindow } from "@tauri-apps/api/window";
 import { invoke } from "@tauri-apps/api/tauri";
 import type { HistoryEntry, ErrorLog } from "./types";
 import { appStorage } from "./utils/storage";
+import { applyRoutingRules } from "./utils/network";
 import { useWindowState } from "./hooks/useWindowState";
 import { useTheme } from "./hooks/useTheme";
 import {
@@ -11,6 +12,21 @@ import {
   HistorySection,
   ErrorLogSection,
   AutoStartSection,
+  EndpointSection,
+  DeeplSection,
+  OpenAiSection,
+  OllamaSection,
+  LibreTranslateSection,
+  AzureSection,
+  GoogleCloudSection,
+  GeminiGenerationSection,
+  AnthropicSection,
+  OfflineSection,
+  FallbackSection,
+  RoutingSection,
+  NetworkSection,
+  RateLimitSection,
+  CacheSection,
 } from "./components/settings";
 import "./Settings.css";
 
@@ -70,6 +86,7 @@ function Settings() {
     try {
       const models = await invoke<string[]>("get_gemini_models", {
         apiKey: apiKey,
+        baseUrl: appStorage.getProviderBaseUrl("gemini") || null,
       });
       console.log("Fetched models:", models);
       setAvailableModels(models);
@@ -99,6 +116,10 @@ function Settings() {
     appStorage.setGeminiModel(geminiModel);
     appStorage.setTargetLanguage(targetLanguage);
     appStorage.setHotkey(hotkey);
+    // The target language is the routing rules' default target
+    applyRoutingRules().catch((err) =>
+      console.error("Failed to apply routing rules:", err)
+    );
 
     alert("設定を保存しました！");
   };
@@ -220,6 +241,24 @@ function Settings() {
           </small>
         </div>
 
+        <GeminiGenerationSection />
+
+        <AnthropicSection />
+
+        <DeeplSection />
+
+        <OpenAiSection />
+
+        <OllamaSection />
+
+        <LibreTranslateSection />
+
+        <AzureSection />
+
+        <GoogleCloudSection />
+
+        <OfflineSection />
+
         <div className="form-group">
           <label>デフォルト翻訳先言語</label>
           <select
@@ -251,6 +290,16 @@ function Settings() {
           </button>
         </div>
 
+        <RoutingSection />
+
+        <FallbackSection />
+
+        <EndpointSection />
+
+        <NetworkSection />
+
+        <RateLimitSection />
+
         <AutoStartSection />
 
         <ThemeSection theme={theme} onThemeChange={setTheme} />
@@ -260,6 +309,8 @@ function Settings() {
           onHistoryChange={setHistoryEntries}
         />
 
+        <CacheSection />
+
         <ErrorLogSection logs={errorLogs} onLogsChange={setErrorLogs} />
 
         <div className="app-info">
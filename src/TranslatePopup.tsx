@@ -2,16 +2,37 @@ import { useState, useEffect, useRef } from "react";
 import { invoke } from "@tauri-apps/api/tauri";
 import { listen } from "@tauri-apps/api/event";
 import { appWindow, LogicalPosition, LogicalSize } from "@tauri-apps/api/window";
-import type { TranslationResult, HistoryEntry, ErrorLog } from "./types";
+import type {
+  TranslationResult,
+  HistoryEntry,
+  ErrorLog,
+  ProviderInfo,
+  TranslationOutcome,
+  DetectedLanguage,
+  TranslationChunk,
+  DictionaryEntry,
+  ResolvedDirection,
+} from "./types";
 import { appStorage } from "./utils/storage";
+import {
+  getProviderSettings,
+  isProviderEnabled,
+  formatServiceName,
+  getFallbackChain,
+} from "./utils/providers";
+import {
+  applyCachePersistence,
+  applyNetworkSettings,
+  applyRateLimits,
+  applyRoutingRules,
+  describeError,
+} from "./utils/network";
 import { useWindowState } from "./hooks/useWindowState";
 import { useTheme } from "./hooks/useTheme";
 import "./TranslatePopup.css";
 
-interface GeminiTranslationResult {
-  translated_text: string;
-  model_used: string;
-}
+// Loading/streaming slot of the fallback-chain request (other slots are provider ids)
+const CHAIN_SLOT = "chain";
 
 function TranslatePopup() {
   const [translations, setTranslations] = useState<TranslationResult[]>([]);
@@ -19,14 +40,32 @@ function TranslatePopup() {
   const [originalText, setOriginalText] = useState("");
   const [copiedKey, setCopiedKey] = useState<string | null>(null);
   const [isResizing, setIsResizing] = useState(false);
-  const [loadingGoogle, setLoadingGoogle] = useState(false);
-  const [loadingGemini, setLoadingGemini] = useState(false);
+  const [providers, setProviders] = useState<ProviderInfo[]>([]);
+  const [loadingProviders, setLoadingProviders] = useState<string[]>([]);
+  // e.g. "Gemini → DeepL → Google" while a fallback chain is running
+  const [chainLabel, setChainLabel] = useState("");
+  // Partial output of streaming providers, keyed by slot (provider id or chain)
+  const [streamingTexts, setStreamingTexts] = useState<Record<string, string>>(
+    {}
+  );
   const [detectedLangState, setDetectedLangState] = useState<string>("unknown");
   const [currentTargetLang, setCurrentTargetLang] = useState<string>("ja");
   const [manualTargetLang, setManualTargetLang] = useState<string | null>(null);
+  // Target chosen by the routing rules for the current text
+  const [autoTargetLang, setAutoTargetLang] = useState<string>("ja");
   const [alwaysOnTop, setAlwaysOnTop] = useState<boolean>(false);
-  const completionTimerRef = useRef<number | null>(null);
+  // Translation whose requests have all been started; loading ends once none is still running
+  const [dispatchedId, setDispatchedId] = useState(0);
+  const pendingCompletionRef = useRef<{
+    translationId: number;
+    text: string;
+    detectedLang: string;
+    targetLang: string;
+  } | null>(null);
   const translationIdRef = useRef<number>(0);
+  // Request ids of provider calls still running in the backend
+  const activeRequestIdsRef = useRef<Set<string>>(new Set());
+  const providersRef = useRef<ProviderInfo[]>([]);
   const isDraggingRef = useRef<boolean>(false); // ref (for immediate focus-loss guard)
   // Grace period after mount to ignore transient blur/focus churn
   const mountTimeRef = useRef<number>(Date.now());
@@ -108,25 +147,43 @@ function TranslatePopup() {
     window.addEventListener("mouseup", onUp);
   };
 
-  // Prefer Gemini above Google in the list
-  const serviceOrder = (service: string) => {
-    if (service.startsWith("Gemini")) return 0;
-    if (service.startsWith("Google")) return 1;
-    return 2;
+  // Results follow the backend registry order (Gemini above Google)
+  const serviceOrder = (providerId: string) => {
+    const index = providersRef.current.findIndex((p) => p.id === providerId);
+    return index === -1 ? providersRef.current.length : index;
   };
 
   const addTranslation = (item: TranslationResult) => {
     setTranslations((prev) => {
       const next = [...prev, item];
       next.sort(
-        (a, b) =>
-          serviceOrder(a.translationService) -
-          serviceOrder(b.translationService)
+        (a, b) => serviceOrder(a.providerId) - serviceOrder(b.providerId)
       );
       return next;
     });
   };
 
+  const setProviderLoading = (providerId: string, loading: boolean) => {
+    setLoadingProviders((prev) =>
+      loading
+        ? [...prev.filter((id) => id !== providerId), providerId]
+        : prev.filter((id) => id !== providerId)
+    );
+  };
+
+  const loadProviders = async (): Promise<ProviderInfo[]> => {
+    if (providersRef.current.length === 0) {
+      try {
+        providersRef.current = await invoke<ProviderInfo[]>("list_providers");
+        setProviders(providersRef.current);
+      } catch (error) {
+        console.error("Failed to load providers:", error);
+        logError("Provider List", String(error));
+      }
+    }
+    return providersRef.current;
+  };
+
   // Save translation to history
   const saveToHistory = (
     originalText: string,
@@ -150,17 +207,20 @@ function TranslatePopup() {
   };
 
   const handleTranslate = async (text: string) => {
-    // Cancel previous translation by incrementing ID
+    // Cancel previous translation by incrementing ID and aborting its backend requests
     translationIdRef.current += 1;
     const currentTranslationId = translationIdRef.current;
+    for (const requestId of activeRequestIdsRef.current) {
+      invoke("cancel_translation", { requestId }).catch(() => {});
+    }
+    activeRequestIdsRef.current.clear();
 
+    pendingCompletionRef.current = null;
     setIsLoading(true);
     setTranslations([]);
-    setLoadingGoogle(false);
-    setLoadingGemini(false);
+    setLoadingProviders([]);
+    setStreamingTexts({});
 
-    const geminiApiKey = appStorage.getGeminiApiKey();
-    const geminiModel = appStorage.getGeminiModel();
     const targetLang = appStorage.getTargetLanguage();
 
     // Declare these variables at function scope so they're accessible in finally block
@@ -168,137 +228,184 @@ function TranslatePopup() {
     let chosenTarget = targetLang;
 
     try {
-      // Detect language
+      // Resolve the direction with the backend routing rules (detection included)
       let detectedLang = "unknown";
-      try {
-        detectedLang = await invoke<string>("detect_language", { text });
-
-        // Check if this translation is still current
-        if (currentTranslationId !== translationIdRef.current) {
-          console.log("Translation cancelled - newer request started");
-          return;
-        }
-
-        detectedLangState = detectedLang;
-        setDetectedLangState(detectedLang);
-      } catch (error) {
-        console.warn("Language detection failed:", error);
-        logError("Language Detection", String(error));
-      }
-
-      // Choose target
-      let autoTargetLang = targetLang;
-      if (detectedLang === "ja") autoTargetLang = "en";
-      else if (detectedLang === "en") autoTargetLang = "ja";
-      chosenTarget = manualTargetLang ?? autoTargetLang;
-      setCurrentTargetLang(chosenTarget);
-
-      // Google request
-      setLoadingGoogle(true);
-      (async () => {
+      let preferredProviders: string[] = [];
+      // With a manually chosen target the direction is already known, so the extra detection
+      // round trip is skipped; providers report the detected language with their result
+      if (manualTargetLang === null) {
         try {
-          const googleResult = await invoke<string>("translate_text", {
+          const baseUrl = getProviderSettings("google").base_url;
+          // With the free Google endpoint switched off, the selection is not sent there for detection
+          const allowNetwork = appStorage.getProviderConfig("google").enabled !== false;
+          let direction = await invoke<ResolvedDirection>("resolve_direction", {
             text,
-            targetLang: chosenTarget,
-            sourceLang: detectedLang === "unknown" ? "auto" : detectedLang,
+            baseUrl,
+            allowNetwork,
           });
+          if (currentTranslationId !== translationIdRef.current) {
+            console.log("Translation cancelled - newer request started");
+            return;
+          }
 
-          // Only add result if this is still the current translation
-          if (currentTranslationId === translationIdRef.current) {
-            addTranslation({
-              originalText: text,
-              translatedText: googleResult,
-              detectedLanguage: detectedLang,
-              targetLanguage: chosenTarget,
-              translationService: "Google (Free)",
-            });
+          // Google may be blocked; fall back to a self-hosted LibreTranslate server if enabled
+          // (a low-confidence local guess is kept only when that fails too)
+          if (direction.detected_language === null || direction.low_confidence) {
+            if (direction.detected_language === null) {
+              logError("Language Detection", "言語を検出できませんでした");
+            }
+            const libre = (await loadProviders()).find(
+              (p) => p.id === "libretranslate"
+            );
+            const libreSettings = getProviderSettings("libretranslate");
+            if (libre && isProviderEnabled(libre, libreSettings)) {
+              try {
+                const ranked = await invoke<DetectedLanguage[]>(
+                  "detect_with_provider",
+                  { providerId: "libretranslate", text, settings: libreSettings }
+                );
+                if (currentTranslationId !== translationIdRef.current) return;
+                if (ranked.length > 0) {
+                  direction = await invoke<ResolvedDirection>(
+                    "resolve_direction",
+                    { text, sourceLang: ranked[0].language, baseUrl }
+                  );
+                }
+              } catch (libreError) {
+                logError("Language Detection (LibreTranslate)", describeError(libreError));
+              }
+            }
           }
+
+          detectedLang = direction.detected_language ?? "unknown";
+          detectedLangState = detectedLang;
+          setDetectedLangState(detectedLang);
+          chosenTarget = direction.target_lang;
+          preferredProviders = direction.providers;
+          setAutoTargetLang(direction.target_lang);
         } catch (error) {
-          console.error("Google Translate failed:", error);
-          logError("Google Translate", String(error));
-        } finally {
-          setLoadingGoogle(false);
+          console.warn("Resolving translation direction failed:", error);
+          logError("Language Detection", describeError(error));
         }
-      })();
+      } else {
+        chosenTarget = manualTargetLang;
+      }
+      setCurrentTargetLang(chosenTarget);
 
-      // Gemini request
-      if (geminiApiKey) {
-        setLoadingGemini(true);
+      const sourceLang = detectedLang === "unknown" ? "auto" : detectedLang;
+      const available = await loadProviders();
+      const enabled = available.filter((p) =>
+        isProviderEnabled(p, getProviderSettings(p.id))
+      );
+      // A routing rule may name preferred providers for this pair (in its order)
+      const preferred = preferredProviders
+        .map((id) => enabled.find((p) => p.id === id))
+        .filter((p): p is ProviderInfo => p !== undefined);
+      const candidates = preferred.length > 0 ? preferred : enabled;
+
+      // Starts one backend request; `slot` identifies it in loading/streaming state
+      const runRequest = (
+        slot: string,
+        label: string,
+        command: string,
+        args: Record<string, unknown>
+      ) => {
+        const requestId = `${currentTranslationId}:${slot}`;
+        activeRequestIdsRef.current.add(requestId);
+        setProviderLoading(slot, true);
         (async () => {
           try {
-            const languageNames: { [key: string]: string } = {
-              ja: "Japanese",
-              en: "English",
-              zh: "Chinese",
-              ko: "Korean",
-              fr: "French",
-              de: "German",
-              es: "Spanish",
-            };
-            const modelToUse = geminiModel === "auto" ? null : geminiModel;
-            const geminiResult = await invoke<GeminiTranslationResult>("translate_with_gemini", {
+            const outcome = await invoke<TranslationOutcome>(command, {
+              ...args,
               text,
-              targetLang: languageNames[chosenTarget] || chosenTarget,
-              apiKey: geminiApiKey,
-              model: modelToUse,
+              sourceLang,
+              targetLang: chosenTarget,
+              requestId,
             });
 
             // Only add result if this is still the current translation
             if (currentTranslationId === translationIdRef.current) {
-              const modelDisplay = geminiResult.model_used;
+              // Detection was skipped or failed; use what the provider detected
+              if (detectedLangState === "unknown" && outcome.detected_language) {
+                detectedLangState = outcome.detected_language.language;
+                setDetectedLangState(detectedLangState);
+              }
+              for (const failure of outcome.fallback_errors) {
+                logError(failure.provider_name, failure.error);
+              }
               addTranslation({
+                providerId: outcome.provider_id,
                 originalText: text,
-                translatedText: geminiResult.translated_text,
-                detectedLanguage: detectedLang,
+                translatedText: outcome.translated_text,
+                detectedLanguage:
+                  outcome.detected_language?.language ?? detectedLang,
+                detectionConfidence: outcome.detected_language?.confidence,
+                attempts: outcome.attempts,
+                cached: outcome.cached,
+                dictionary: outcome.dictionary ?? undefined,
+                latencyMs: outcome.latency_ms,
+                fallbackFrom: outcome.fallback_errors.map(
+                  (f) => f.provider_name
+                ),
                 targetLanguage: chosenTarget,
-                translationService: `Gemini (${modelDisplay})`,
+                translationService: formatServiceName(
+                  outcome.provider_name,
+                  outcome.model_used
+                ),
               });
             }
           } catch (error) {
-            console.error("Gemini translation failed:", error);
-            logError("Gemini Translation", String(error));
+            // Superseded requests are cancelled on purpose; not worth logging
+            if (currentTranslationId === translationIdRef.current) {
+              console.error(`${label} translation failed:`, error);
+              logError(label, describeError(error));
+            }
           } finally {
-            setLoadingGemini(false);
+            activeRequestIdsRef.current.delete(requestId);
+            if (currentTranslationId === translationIdRef.current) {
+              setProviderLoading(slot, false);
+            }
           }
         })();
+      };
+
+      // Providers in this pair's fallback chain produce a single result from the first that answers
+      const chain = getFallbackChain(detectedLang, chosenTarget);
+      const chainProviders = (chain?.providers ?? [])
+        .map((id) => enabled.find((p) => p.id === id))
+        .filter((p): p is ProviderInfo => p !== undefined);
+      if (chainProviders.length > 0) {
+        const label = chainProviders.map((p) => p.display_name).join(" → ");
+        setChainLabel(label);
+        runRequest(CHAIN_SLOT, label, "translate_with_fallback", {
+          providerIds: chainProviders.map((p) => p.id),
+          settings: Object.fromEntries(
+            chainProviders.map((p) => [p.id, getProviderSettings(p.id)])
+          ),
+        });
+      }
+
+      // One request per remaining enabled provider, rendered as results arrive
+      for (const provider of candidates) {
+        if (chainProviders.includes(provider)) continue;
+        runRequest(provider.id, provider.display_name, "translate", {
+          providerId: provider.id,
+          settings: getProviderSettings(provider.id),
+        });
       }
     } catch (error) {
       console.error("Translation failed:", error);
     } finally {
-      // Clear any existing completion timer
-      if (completionTimerRef.current !== null) {
-        clearInterval(completionTimerRef.current);
-        completionTimerRef.current = null;
+      // Completion is picked up by the effect below once every request has settled
+      if (currentTranslationId === translationIdRef.current) {
+        pendingCompletionRef.current = {
+          translationId: currentTranslationId,
+          text,
+          detectedLang: detectedLangState,
+          targetLang: chosenTarget,
+        };
+        setDispatchedId(currentTranslationId);
       }
-
-      // Set timeout to prevent infinite polling (max 10 seconds)
-      const startTime = Date.now();
-      const MAX_WAIT_TIME = 10000;
-
-      completionTimerRef.current = window.setInterval(() => {
-        if (!loadingGoogle && !loadingGemini) {
-          setIsLoading(false);
-          if (completionTimerRef.current !== null) {
-            clearInterval(completionTimerRef.current);
-            completionTimerRef.current = null;
-          }
-
-          // Save to history after all translations complete
-          if (translations.length > 0) {
-            saveToHistory(text, detectedLangState, chosenTarget, translations);
-          }
-        } else if (Date.now() - startTime > MAX_WAIT_TIME) {
-          // Timeout: force completion
-          console.warn("Translation completion timeout - forcing stop");
-          setIsLoading(false);
-          setLoadingGoogle(false);
-          setLoadingGemini(false);
-          if (completionTimerRef.current !== null) {
-            clearInterval(completionTimerRef.current);
-            completionTimerRef.current = null;
-          }
-        }
-      }, 100);
     }
   };
 
@@ -320,14 +427,9 @@ function TranslatePopup() {
   };
 
   const handleSwapLanguages = () => {
-    const defaultAuto =
-      detectedLangState === "ja"
-        ? "en"
-        : detectedLangState === "en"
-        ? "ja"
-        : currentTargetLang;
+    // Swapping back returns to the routing rules' choice
     const next =
-      currentTargetLang === detectedLangState ? defaultAuto : detectedLangState;
+      currentTargetLang === detectedLangState ? autoTargetLang : detectedLangState;
     setManualTargetLang(next);
     if (originalText.trim()) {
       handleTranslate(originalText.trim());
@@ -354,6 +456,20 @@ function TranslatePopup() {
     setAlwaysOnTop(pinPref);
     appWindow.setAlwaysOnTop(pinPref).catch(() => {});
 
+    // The popup window is created at startup, so it hands the saved proxy settings to the backend
+    applyNetworkSettings().catch((err) =>
+      console.error("Failed to apply network settings:", err)
+    );
+    applyRateLimits().catch((err) =>
+      console.error("Failed to apply rate limits:", err)
+    );
+    applyRoutingRules().catch((err) =>
+      console.error("Failed to apply routing rules:", err)
+    );
+    applyCachePersistence().catch((err) =>
+      console.error("Failed to apply cache settings:", err)
+    );
+
     const unlisten = listen<string>("translate-shortcut", async (event) => {
       const selectedText = event.payload;
       if (selectedText && selectedText.trim()) {
@@ -362,8 +478,21 @@ function TranslatePopup() {
       }
     });
 
+    // Streamed partial output; chunks from superseded translations are dropped
+    const unlistenChunk = listen<TranslationChunk>(
+      "translation-chunk",
+      (event) => {
+        const { request_id, text } = event.payload;
+        const prefix = `${translationIdRef.current}:`;
+        if (!request_id.startsWith(prefix)) return;
+        const slot = request_id.slice(prefix.length);
+        setStreamingTexts((prev) => ({ ...prev, [slot]: text }));
+      }
+    );
+
     return () => {
       unlisten.then((fn) => fn());
+      unlistenChunk.then((fn) => fn());
     };
   }, []);
 
@@ -411,15 +540,28 @@ function TranslatePopup() {
     };
   }, [alwaysOnTop]);
 
-  // Cleanup completion timer on unmount
+  // End loading and save history when the dispatched translation has no request left running.
+  // No fixed cutoff: each backend request ends with its own timeout (or its stream's)
   useEffect(() => {
-    return () => {
-      if (completionTimerRef.current !== null) {
-        clearInterval(completionTimerRef.current);
-        completionTimerRef.current = null;
-      }
-    };
-  }, []);
+    const pending = pendingCompletionRef.current;
+    if (
+      !pending ||
+      pending.translationId !== dispatchedId ||
+      loadingProviders.length > 0
+    ) {
+      return;
+    }
+    pendingCompletionRef.current = null;
+    setIsLoading(false);
+    if (translations.length > 0) {
+      saveToHistory(
+        pending.text,
+        pending.detectedLang,
+        pending.targetLang,
+        translations
+      );
+    }
+  }, [dispatchedId, loadingProviders, translations]);
 
   return (
     <div className={`translate-popup${isResizing ? " resizing" : ""}`}>
@@ -486,13 +628,14 @@ function TranslatePopup() {
         </div>
       </div>
 
-      {translations.length === 0 && !isLoading && (
+      {!isLoading && loadingProviders.length === 0 && translations.length === 0 && (
         <div className="no-result">
           <p>テキストを選択して Ctrl+Shift+Q を押してください</p>
         </div>
       )}
 
-      {translations.length > 0 && (
+      {/* Shown while requests run too, so streamed text appears before the first full result */}
+      {(isLoading || loadingProviders.length > 0 || translations.length > 0) && (
         <div className="results">
           <div className="original-text">
             <div className="original-header">
@@ -515,29 +658,43 @@ function TranslatePopup() {
             <div className="text">{originalText}</div>
           </div>
 
-          {/* Per-service loading placeholders */}
-          {loadingGemini &&
-            !translations.some((t) => t.translationService.startsWith("Gemini")) && (
-              <div className="translation-item">
-                <div className="service-name">
-                  <span className="service-label">Gemini</span>
-                  <div className="service-actions">
-                    <span className="lang-info">
-                      {detectedLangState} → {currentTargetLang}
-                    </span>
-                  </div>
-                </div>
-                <div className="translated-text">
-                  <span className="inline-spinner" /> 翻訳中…
+          {/* Fallback chain loading placeholder */}
+          {loadingProviders.includes(CHAIN_SLOT) && (
+            <div key="loading-chain" className="translation-item">
+              <div className="service-name">
+                <span className="service-label">{chainLabel}</span>
+                <div className="service-actions">
+                  <span className="lang-info">
+                    {detectedLangState} → {currentTargetLang}
+                  </span>
                 </div>
               </div>
-            )}
+              <div className="translated-text">
+                {streamingTexts[CHAIN_SLOT] ? (
+                  <>
+                    {streamingTexts[CHAIN_SLOT]}
+                    <span className="inline-spinner" />
+                  </>
+                ) : (
+                  <>
+                    <span className="inline-spinner" /> 翻訳中…
+                  </>
+                )}
+              </div>
+            </div>
+          )}
 
-          {loadingGoogle &&
-            !translations.some((t) => t.translationService.startsWith("Google")) && (
-              <div className="translation-item">
+          {/* Per-service loading placeholders */}
+          {providers
+            .filter(
+              (p) =>
+                loadingProviders.includes(p.id) &&
+                !translations.some((t) => t.providerId === p.id)
+            )
+            .map((p) => (
+              <div key={`loading-${p.id}`} className="translation-item">
                 <div className="service-name">
-                  <span className="service-label">Google (Free)</span>
+                  <span className="service-label">{p.display_name}</span>
                   <div className="service-actions">
                     <span className="lang-info">
                       {detectedLangState} → {currentTargetLang}
@@ -545,20 +702,66 @@ function TranslatePopup() {
                   </div>
                 </div>
                 <div className="translated-text">
-                  <span className="inline-spinner" /> 翻訳中…
+                  {streamingTexts[p.id] ? (
+                    <>
+                      {streamingTexts[p.id]}
+                      <span className="inline-spinner" />
+                    </>
+                  ) : (
+                    <>
+                      <span className="inline-spinner" /> 翻訳中…
+                    </>
+                  )}
                 </div>
               </div>
-            )}
+            ))}
 
           {translations.map((result, index) => (
             <div key={index} className="translation-item">
               <div className="service-name">
                 <span className="service-label">
                   {result.translationService}
+                  {result.fallbackFrom && result.fallbackFrom.length > 0 && (
+                    <span
+                      className="retry-info"
+                      title={`${result.fallbackFrom.join(", ")} が失敗したため代替しました`}
+                    >
+                      {" "}
+                      (代替: {result.fallbackFrom.join(", ")}失敗)
+                    </span>
+                  )}
+                  {result.attempts !== undefined && result.attempts > 1 && (
+                    <span
+                      className="retry-info"
+                      title={`${result.attempts}回目の試行で成功しました`}
+                    >
+                      {" "}
+                      (再試行 {result.attempts - 1}回)
+                    </span>
+                  )}
+                  {result.cached && (
+                    <span
+                      className="retry-info"
+                      title="以前の翻訳結果を再利用しました（APIは呼び出していません）"
+                    >
+                      {" "}
+                      (キャッシュ)
+                    </span>
+                  )}
                 </span>
                 <div className="service-actions">
-                  <span className="lang-info">
-                    {result.detectedLanguage} → {result.targetLanguage}
+                  <span
+                    className="lang-info"
+                    title={
+                      result.latencyMs !== undefined
+                        ? `${result.latencyMs}ms`
+                        : undefined
+                    }
+                  >
+                    {result.detectionConfidence !== undefined
+                      ? `検出: ${result.detectedLanguage} (${result.detectionConfidence.toFixed(2)})`
+                      : result.detectedLanguage}{" "}
+                    → {result.targetLanguage}
                   </span>
                   <button
                     className="copy-btn"
@@ -572,6 +775,9 @@ function TranslatePopup() {
                 </div>
               </div>
               <div className="translated-text">{result.translatedText}</div>
+              {result.dictionary && (
+                <DictionaryView entry={result.dictionary} />
+              )}
             </div>
           ))}
         </div>
@@ -580,4 +786,47 @@ function TranslatePopup() {
   );
 }
 
+// Alternative translations, transliteration, synonyms and examples for a dictionary lookup
+function DictionaryView({ entry }: { entry: DictionaryEntry }) {
+  const transliteration = [entry.source_transliteration, entry.transliteration]
+    .filter((t) => t)
+    .join(" → ");
+  return (
+    <div className="dictionary">
+      {transliteration && (
+        <div className="dictionary-translit">{transliteration}</div>
+      )}
+      {entry.senses.map((sense, i) => (
+        <div key={`s-${i}`} className="dictionary-sense">
+          <span className="dictionary-pos">{sense.part_of_speech}</span>
+          {sense.terms.slice(0, 6).map((term, j) => (
+            <span
+              key={j}
+              className="dictionary-term"
+              title={term.reverse_translations.join(", ")}
+            >
+              {j > 0 && ", "}
+              {term.text}
+            </span>
+          ))}
+        </div>
+      ))}
+      {entry.synonyms.map((sense, i) => (
+        <div key={`y-${i}`} className="dictionary-sense">
+          <span className="dictionary-pos">類義語 ({sense.part_of_speech})</span>
+          {sense.terms
+            .slice(0, 8)
+            .map((term) => term.text)
+            .join(", ")}
+        </div>
+      ))}
+      {entry.examples.map((example, i) => (
+        <div key={`e-${i}`} className="dictionary-example">
+          {example}
+        </div>
+      ))}
+    </div>
+  );
+}
+
 export default TranslatePopup;
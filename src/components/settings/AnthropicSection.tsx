@@ -0,0 +1,106 @@
+import { useState, useEffect } from "react";
+import { invoke } from "@tauri-apps/api/tauri";
+import { appStorage } from "../../utils/storage";
+import { getProviderSettings } from "../../utils/providers";
+
+export function AnthropicSection() {
+  const [apiKey, setApiKey] = useState("");
+  const [model, setModel] = useState("auto");
+  const [maxTokens, setMaxTokens] = useState("4096");
+  const [availableModels, setAvailableModels] = useState<string[]>([]);
+  const [isLoadingModels, setIsLoadingModels] = useState(false);
+  const [modelError, setModelError] = useState("");
+
+  useEffect(() => {
+    const config = appStorage.getProviderConfig("anthropic");
+    setApiKey(config.apiKey || "");
+    setModel(config.model || "auto");
+    setMaxTokens(String(config.options?.max_tokens ?? "4096"));
+  }, []);
+
+  const buildConfig = () => ({
+    apiKey: apiKey.trim(),
+    model: model === "auto" ? "" : model,
+    options: { max_tokens: parseInt(maxTokens, 10) || 4096 },
+  });
+
+  const fetchModels = async () => {
+    appStorage.setProviderConfig("anthropic", buildConfig());
+    setIsLoadingModels(true);
+    setModelError("");
+    try {
+      const models = await invoke<string[]>("list_models", {
+        providerId: "anthropic",
+        settings: getProviderSettings("anthropic"),
+      });
+      setAvailableModels(models);
+    } catch (error) {
+      console.error("Failed to fetch Anthropic models:", error);
+      setModelError(`エラー: ${error}`);
+    } finally {
+      setIsLoadingModels(false);
+    }
+  };
+
+  const handleSave = () => {
+    appStorage.setProviderConfig("anthropic", buildConfig());
+    alert("Anthropicの設定を保存しました");
+  };
+
+  return (
+    <div className="form-group">
+      <label>Anthropic API Key (オプション)</label>
+      <input
+        type="password"
+        value={apiKey}
+        onChange={(e) => setApiKey(e.target.value)}
+        placeholder="Anthropic APIキーを入力"
+        className="input-field"
+      />
+      <div className="flex-row mt-10">
+        <select
+          value={model}
+          onChange={(e) => setModel(e.target.value)}
+          className="select-field flex-1"
+          title="Anthropic Model"
+          disabled={!apiKey || isLoadingModels}
+        >
+          <option value="auto">自動（最新のHaikuモデル）</option>
+          {model !== "auto" && !availableModels.includes(model) && (
+            <option value={model}>{model}</option>
+          )}
+          {availableModels.map((m) => (
+            <option key={m} value={m}>
+              {m}
+            </option>
+          ))}
+        </select>
+        <input
+          type="number"
+          min={256}
+          value={maxTokens}
+          onChange={(e) => setMaxTokens(e.target.value)}
+          title="max_tokens"
+          className="input-field min-w-80"
+        />
+        <button
+          onClick={fetchModels}
+          disabled={!apiKey || isLoadingModels}
+          className="save-button p-8-16 fs-14 min-w-auto"
+        >
+          {isLoadingModels ? "取得中..." : "更新"}
+        </button>
+        <button
+          onClick={handleSave}
+          className="save-button p-8-16 fs-14 min-w-auto"
+        >
+          保存
+        </button>
+      </div>
+      <small className={modelError ? "error-text" : ""}>
+        {modelError ||
+          "キーを入力するとGeminiと並んでAnthropicの翻訳結果が表示されます（右の数値は max_tokens）"}
+      </small>
+    </div>
+  );
+}
@@ -0,0 +1,50 @@
+import { useState, useEffect } from "react";
+import { appStorage } from "../../utils/storage";
+
+export function AzureSection() {
+  const [apiKey, setApiKey] = useState("");
+  const [region, setRegion] = useState("");
+
+  useEffect(() => {
+    const config = appStorage.getProviderConfig("azure");
+    setApiKey(config.apiKey || "");
+    setRegion(String(config.options?.region ?? ""));
+  }, []);
+
+  const handleSave = () => {
+    appStorage.setProviderConfig("azure", {
+      apiKey: apiKey.trim(),
+      options: { region: region.trim() || null },
+    });
+    alert("Microsoft Translatorの設定を保存しました");
+  };
+
+  return (
+    <div className="form-group">
+      <label>Microsoft Translator (Azure) キー (オプション)</label>
+      <input
+        type="password"
+        value={apiKey}
+        onChange={(e) => setApiKey(e.target.value)}
+        placeholder="サブスクリプションキーを入力"
+        className="input-field"
+      />
+      <div className="flex-row mt-10">
+        <input
+          type="text"
+          value={region}
+          onChange={(e) => setRegion(e.target.value)}
+          placeholder="リージョン（例: japaneast、グローバルの場合は空欄）"
+          className="input-field flex-1"
+        />
+        <button
+          onClick={handleSave}
+          className="save-button p-8-16 fs-14 min-w-auto"
+        >
+          保存
+        </button>
+      </div>
+      <small>Azure Portalの「キーとエンドポイント」に表示されるキーとリージョンを入力してください</small>
+    </div>
+  );
+}
@@ -0,0 +1,135 @@
+import { useState } from "react";
+import { invoke } from "@tauri-apps/api/tauri";
+import type { CacheEntryInfo, CacheStats } from "../../types";
+import { appStorage } from "../../utils/storage";
+import { applyCachePersistence } from "../../utils/network";
+
+export function CacheSection() {
+  const [showCache, setShowCache] = useState(false);
+  const [stats, setStats] = useState<CacheStats | null>(null);
+  const [entries, setEntries] = useState<CacheEntryInfo[]>([]);
+  const [persist, setPersist] = useState(appStorage.getCachePersist());
+
+  const refresh = async () => {
+    try {
+      const [nextStats, nextEntries] = await Promise.all([
+        invoke<CacheStats>("get_cache_stats"),
+        invoke<CacheEntryInfo[]>("list_cache_entries", { limit: 50 }),
+      ]);
+      setStats(nextStats);
+      setEntries(nextEntries);
+    } catch (err) {
+      console.error("Failed to load translation cache:", err);
+    }
+  };
+
+  const toggle = () => {
+    if (!showCache) refresh();
+    setShowCache(!showCache);
+  };
+
+  const clearCache = async () => {
+    if (!confirm("翻訳キャッシュをすべて削除しますか？")) return;
+    try {
+      const removed = await invoke<number>("clear_translation_cache");
+      alert(`${removed}件のキャッシュを削除しました`);
+      await refresh();
+    } catch (err) {
+      alert(`キャッシュを削除できません: ${err}`);
+    }
+  };
+
+  const togglePersist = async (enabled: boolean) => {
+    if (
+      !enabled &&
+      !confirm("ディスクに保存した翻訳キャッシュを削除して、保存を無効にしますか？")
+    ) {
+      return;
+    }
+    try {
+      await applyCachePersistence(enabled);
+      appStorage.setCachePersist(enabled);
+      setPersist(enabled);
+      if (showCache) await refresh();
+    } catch (err) {
+      alert(`キャッシュの設定を適用できません: ${err}`);
+    }
+  };
+
+  const formatDate = (unixSeconds: number) =>
+    new Date(unixSeconds * 1000).toLocaleString("ja-JP", {
+      month: "2-digit",
+      day: "2-digit",
+      hour: "2-digit",
+      minute: "2-digit",
+    });
+
+  return (
+    <div className="form-group">
+      <div className="flex-between">
+        <label>翻訳キャッシュ</label>
+        <button
+          onClick={toggle}
+          className="save-button p-4-12 fs-12 min-w-auto"
+        >
+          {showCache ? "非表示" : "表示"}
+        </button>
+      </div>
+      <label className="flex-row-10 mt-10">
+        <input
+          type="checkbox"
+          checked={persist}
+          onChange={(e) => togglePersist(e.target.checked)}
+        />
+        <span className="text-secondary">
+          翻訳結果をディスクにも保存する（アプリ再起動後も利用）
+        </span>
+      </label>
+      {showCache && (
+        <div className="mt-10">
+          <div className="mb-10">
+            <button
+              onClick={clearCache}
+              className="save-button p-4-12 fs-12 bg-red"
+            >
+              キャッシュをクリア
+            </button>
+            {stats && (
+              <small className="ml-10 color-666">
+                {stats.disk_entries}件 ({(stats.disk_bytes / 1024).toFixed(0)} KB)
+                / 今回の起動でヒット {stats.hits}回・ミス {stats.misses}回
+              </small>
+            )}
+          </div>
+          <div className="history-container">
+            {entries.length === 0 ? (
+              <p className="history-empty">キャッシュはありません</p>
+            ) : (
+              entries.map((entry, index) => (
+                <div key={index} className="history-item">
+                  <div className="history-header">
+                    <span>{formatDate(entry.created_at)}</span>
+                    <span>
+                      {entry.provider_id}
+                      {entry.model !== "default" && ` (${entry.model})`} ・{" "}
+                      {entry.source_lang} → {entry.target_lang}
+                    </span>
+                  </div>
+                  <div className="history-text mb-4 fw-500">
+                    {entry.text_preview}
+                  </div>
+                  <div className="history-translation">
+                    {entry.translation_preview}
+                  </div>
+                </div>
+              ))
+            )}
+          </div>
+        </div>
+      )}
+      <small>
+        同じ文章の再選択時はAPIを呼び出さずに即座に表示します。既定ではメモリ上にのみ保持し、アプリ終了時に破棄されます。ディスク保存を有効にすると、選択テキストと翻訳結果がアプリのデータフォルダに最大30日間平文で保存されます
+      </small>
+    </div>
+  );
+}
@@ -0,0 +1,97 @@
+import { useState, useEffect } from "react";
+import { appStorage } from "../../utils/storage";
+
+export function DeeplSection() {
+  const [apiKey, setApiKey] = useState("");
+  const [plan, setPlan] = useState("auto");
+  const [formality, setFormality] = useState("default");
+  const [splitSentences, setSplitSentences] = useState("1");
+  const [preserveFormatting, setPreserveFormatting] = useState(true);
+
+  useEffect(() => {
+    const config = appStorage.getProviderConfig("deepl");
+    const options = config.options || {};
+    setApiKey(config.apiKey || "");
+    setPlan(String(options.plan ?? "auto"));
+    setFormality(String(options.formality ?? "default"));
+    setSplitSentences(String(options.split_sentences ?? "1"));
+    setPreserveFormatting(options.preserve_formatting !== false);
+  }, []);
+
+  const handleSave = () => {
+    appStorage.setProviderConfig("deepl", {
+      apiKey: apiKey.trim(),
+      options: {
+        plan: plan === "auto" ? null : plan,
+        formality,
+        split_sentences: splitSentences,
+        preserve_formatting: preserveFormatting,
+      },
+    });
+    alert("DeepL設定を保存しました");
+  };
+
+  return (
+    <div className="form-group">
+      <label>DeepL 認証キー (オプション)</label>
+      <input
+        type="password"
+        value={apiKey}
+        onChange={(e) => setApiKey(e.target.value)}
+        placeholder="DeepL APIの認証キーを入力"
+        className="input-field"
+      />
+      <div className="flex-row mt-10">
+        <select
+          value={plan}
+          onChange={(e) => setPlan(e.target.value)}
+          className="select-field flex-1"
+          title="DeepL Plan"
+        >
+          <option value="auto">プラン: 自動判定</option>
+          <option value="free">プラン: Free</option>
+          <option value="pro">プラン: Pro</option>
+        </select>
+        <select
+          value={formality}
+          onChange={(e) => setFormality(e.target.value)}
+          className="select-field flex-1"
+          title="DeepL Formality"
+        >
+          <option value="default">文体: デフォルト</option>
+          <option value="prefer_more">文体: 丁寧</option>
+          <option value="prefer_less">文体: くだけた</option>
+        </select>
+      </div>
+      <div className="flex-row mt-10">
+        <select
+          value={splitSentences}
+          onChange={(e) => setSplitSentences(e.target.value)}
+          className="select-field flex-1"
+          title="DeepL Sentence Splitting"
+        >
+          <option value="1">文分割: 句読点と改行</option>
+          <option value="nonewlines">文分割: 句読点のみ</option>
+          <option value="0">文分割: しない</option>
+        </select>
+        <label className="flex-row-10 self-center">
+          <input
+            type="checkbox"
+            checked={preserveFormatting}
+            onChange={(e) => setPreserveFormatting(e.target.checked)}
+          />
+          <span className="text-secondary">書式を保持</span>
+        </label>
+        <button
+          onClick={handleSave}
+          className="save-button p-8-16 fs-14 min-w-auto"
+        >
+          保存
+        </button>
+      </div>
+      <small>
+        キーを入力するとDeepL翻訳が有効化されます（Freeキーは末尾が「:fx」）
+      </small>
+    </div>
+  );
+}
@@ -0,0 +1,77 @@
+import { useState, useEffect } from "react";
+import { invoke } from "@tauri-apps/api/tauri";
+import type { ProviderInfo } from "../../types";
+import { appStorage } from "../../utils/storage";
+
+export function EndpointSection() {
+  const [showEndpoints, setShowEndpoints] = useState(false);
+  const [providers, setProviders] = useState<ProviderInfo[]>([]);
+  const [baseUrls, setBaseUrls] = useState<Record<string, string>>({});
+
+  useEffect(() => {
+    setBaseUrls(appStorage.getProviderBaseUrls());
+    invoke<ProviderInfo[]>("list_providers")
+      .then(setProviders)
+      .catch((err) => console.error("Failed to load providers:", err));
+  }, []);
+
+  const handleChange = (providerId: string, url: string) => {
+    setBaseUrls((prev) => ({ ...prev, [providerId]: url }));
+  };
+
+  const handleSave = () => {
+    // Drop empty entries so the built-in default is used
+    const trimmed: Record<string, string> = {};
+    for (const [id, url] of Object.entries(baseUrls)) {
+      if (url.trim()) trimmed[id] = url.trim();
+    }
+    appStorage.setProviderBaseUrls(trimmed);
+    setBaseUrls(trimmed);
+    alert("エンドポイント設定を保存しました");
+  };
+
+  return (
+    <div className="form-group">
+      <div className="flex-between">
+        <label>エンドポイント (上級者向け)</label>
+        <button
+          onClick={() => setShowEndpoints(!showEndpoints)}
+          className="save-button p-4-12 fs-12 min-w-auto"
+        >
+          {showEndpoints ? "非表示" : "表示"}
+        </button>
+      </div>
+      {showEndpoints && (
+        <div className="mt-10">
+          {providers
+            // Providers without a network endpoint (offline) have nothing to configure
+            .filter((provider) => provider.default_base_url)
+            .map((provider) => (
+              <div key={provider.id} className="mb-10">
+                <small className="fw-500">{provider.display_name}</small>
+                <input
+                  type="text"
+                  value={baseUrls[provider.id] || ""}
+                  onChange={(e) => handleChange(provider.id, e.target.value)}
+                  placeholder={provider.default_base_url}
+                  className="input-field"
+                />
+                <small className="color-999">
+                  環境変数 {provider.base_url_env} が設定されている場合はそちらが優先されます
+                </small>
+              </div>
+            ))}
+          <button
+            onClick={handleSave}
+            className="save-button p-4-12 fs-12 min-w-auto"
+          >
+            保存
+          </button>
+        </div>
+      )}
+      <small>
+        リバースプロキシやローカルのモックサーバーを使う場合に各プロバイダーのベースURLを変更できます（空欄でデフォルト）
+      </small>
+    </div>
+  );
+}
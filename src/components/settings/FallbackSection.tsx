@@ -0,0 +1,147 @@
+import { useState, useEffect } from "react";
+import { invoke } from "@tauri-apps/api/tauri";
+import type { FallbackChain, ProviderInfo } from "../../types";
+import { appStorage } from "../../utils/storage";
+
+const LANGUAGES = ["*", "ja", "en", "zh", "ko", "fr", "de", "es"];
+
+// Editable row: providers are typed as a comma-separated id list
+interface ChainRow {
+  source: string;
+  target: string;
+  providers: string;
+}
+
+export function FallbackSection() {
+  const [showChains, setShowChains] = useState(false);
+  const [providers, setProviders] = useState<ProviderInfo[]>([]);
+  const [rows, setRows] = useState<ChainRow[]>([]);
+
+  useEffect(() => {
+    setRows(
+      appStorage.getFallbackChains().map((chain) => ({
+        source: chain.source,
+        target: chain.target,
+        providers: chain.providers.join(", "),
+      }))
+    );
+    invoke<ProviderInfo[]>("list_providers")
+      .then(setProviders)
+      .catch((err) => console.error("Failed to load providers:", err));
+  }, []);
+
+  const updateRow = (index: number, patch: Partial<ChainRow>) => {
+    setRows((prev) =>
+      prev.map((row, i) => (i === index ? { ...row, ...patch } : row))
+    );
+  };
+
+  const handleSave = () => {
+    const knownIds = new Set(providers.map((p) => p.id));
+    const chains: FallbackChain[] = [];
+    for (const row of rows) {
+      const ids = row.providers
+        .split(/[,\s→>]+/)
+        .map((id) => id.trim())
+        .filter((id) => id);
+      const unknown = ids.filter((id) => !knownIds.has(id));
+      if (unknown.length > 0) {
+        alert(`不明なプロバイダーIDです: ${unknown.join(", ")}`);
+        return;
+      }
+      if (ids.length > 0) {
+        chains.push({ source: row.source, target: row.target, providers: ids });
+      }
+    }
+    appStorage.setFallbackChains(chains);
+    alert("フォールバック設定を保存しました");
+  };
+
+  return (
+    <div className="form-group">
+      <div className="flex-between">
+        <label>フォールバック (上級者向け)</label>
+        <button
+          onClick={() => setShowChains(!showChains)}
+          className="save-button p-4-12 fs-12 min-w-auto"
+        >
+          {showChains ? "非表示" : "表示"}
+        </button>
+      </div>
+      {showChains && (
+        <div className="mt-10">
+          {rows.map((row, index) => (
+            <div key={index} className="flex-row mb-10">
+              <select
+                value={row.source}
+                onChange={(e) => updateRow(index, { source: e.target.value })}
+                className="input-field"
+                title="元言語"
+              >
+                {LANGUAGES.map((lang) => (
+                  <option key={lang} value={lang}>
+                    {lang === "*" ? "すべて" : lang}
+                  </option>
+                ))}
+              </select>
+              <select
+                value={row.target}
+                onChange={(e) => updateRow(index, { target: e.target.value })}
+                className="input-field"
+                title="翻訳先"
+              >
+                {LANGUAGES.map((lang) => (
+                  <option key={lang} value={lang}>
+                    {lang === "*" ? "すべて" : lang}
+                  </option>
+                ))}
+              </select>
+              <input
+                type="text"
+                value={row.providers}
+                onChange={(e) =>
+                  updateRow(index, { providers: e.target.value })
+                }
+                placeholder="gemini, deepl, google"
+                className="input-field flex-1"
+              />
+              <button
+                onClick={() =>
+                  setRows((prev) => prev.filter((_, i) => i !== index))
+                }
+                className="save-button p-4-12 fs-12 min-w-auto"
+              >
+                削除
+              </button>
+            </div>
+          ))}
+          <div className="flex-row">
+            <button
+              onClick={() =>
+                setRows((prev) => [
+                  ...prev,
+                  { source: "*", target: "*", providers: "" },
+                ])
+              }
+              className="save-button p-4-12 fs-12 min-w-auto"
+            >
+              追加
+            </button>
+            <button
+              onClick={handleSave}
+              className="save-button p-4-12 fs-12 min-w-auto"
+            >
+              保存
+            </button>
+          </div>
+          <small className="color-999">
+            利用可能なID: {providers.map((p) => p.id).join(", ")}
+          </small>
+        </div>
+      )}
+      <small>
+        言語ペアごとにプロバイダーを順番に試し、最初に成功した結果を1件だけ表示します（例: gemini, deepl, google）
+      </small>
+    </div>
+  );
+}
@@ -0,0 +1,216 @@
+import { useState, useEffect } from "react";
+import type { GeminiGenerationSettings } from "../../types";
+import { appStorage } from "../../utils/storage";
+
+const SAFETY_THRESHOLDS = [
+  { value: "", label: "既定" },
+  { value: "BLOCK_NONE", label: "ブロックしない" },
+  { value: "BLOCK_ONLY_HIGH", label: "高のみブロック" },
+  { value: "BLOCK_MEDIUM_AND_ABOVE", label: "中以上をブロック" },
+  { value: "BLOCK_LOW_AND_ABOVE", label: "低以上をブロック" },
+];
+
+// Editable row: numbers are kept as text so empty fields mean "use the default"
+interface SettingsRow {
+  model: string;
+  temperature: string;
+  topP: string;
+  maxOutputTokens: string;
+  thinkingBudget: string;
+  safetyThreshold: string;
+}
+
+const toText = (value: number | undefined) =>
+  value === undefined ? "" : String(value);
+
+const toNumber = (text: string) => {
+  const value = parseFloat(text);
+  return Number.isNaN(value) ? undefined : value;
+};
+
+export function GeminiGenerationSection() {
+  const [showSettings, setShowSettings] = useState(false);
+  const [rows, setRows] = useState<SettingsRow[]>([]);
+
+  useEffect(() => {
+    const models = (appStorage.getProviderConfig("gemini").options?.models ??
+      {}) as Record<string, GeminiGenerationSettings>;
+    setRows(
+      Object.entries(models).map(([model, settings]) => ({
+        model,
+        temperature: toText(settings.temperature),
+        topP: toText(settings.top_p),
+        maxOutputTokens: toText(settings.max_output_tokens),
+        thinkingBudget: toText(settings.thinking_budget),
+        safetyThreshold: settings.safety_threshold ?? "",
+      }))
+    );
+  }, []);
+
+  const updateRow = (index: number, patch: Partial<SettingsRow>) => {
+    setRows((prev) =>
+      prev.map((row, i) => (i === index ? { ...row, ...patch } : row))
+    );
+  };
+
+  const handleSave = () => {
+    const models: Record<string, GeminiGenerationSettings> = {};
+    for (const row of rows) {
+      const model = row.model.trim();
+      if (!model) continue;
+      const temperature = toNumber(row.temperature);
+      if (temperature !== undefined && (temperature < 0 || temperature > 2)) {
+        alert(`temperatureは0〜2で指定してください: ${model}`);
+        return;
+      }
+      const topP = toNumber(row.topP);
+      if (topP !== undefined && (topP < 0 || topP > 1)) {
+        alert(`topPは0〜1で指定してください: ${model}`);
+        return;
+      }
+      const maxOutputTokens = toNumber(row.maxOutputTokens);
+      const thinkingBudget = toNumber(row.thinkingBudget);
+      models[model] = {
+        temperature,
+        top_p: topP,
+        max_output_tokens:
+          maxOutputTokens === undefined ? undefined : Math.round(maxOutputTokens),
+        thinking_budget:
+          thinkingBudget === undefined ? undefined : Math.round(thinkingBudget),
+        safety_threshold: row.safetyThreshold || undefined,
+      };
+    }
+
+    const config = appStorage.getProviderConfig("gemini");
+    appStorage.setProviderConfig("gemini", {
+      ...config,
+      options: { ...config.options, models },
+    });
+    alert("Geminiの生成設定を保存しました");
+  };
+
+  return (
+    <div className="form-group">
+      <div className="flex-between">
+        <label>Gemini 生成設定 (上級者向け)</label>
+        <button
+          onClick={() => setShowSettings(!showSettings)}
+          className="save-button p-4-12 fs-12 min-w-auto"
+        >
+          {showSettings ? "非表示" : "表示"}
+        </button>
+      </div>
+      {showSettings && (
+        <div className="mt-10">
+          {rows.map((row, index) => (
+            <div key={index} className="mb-10">
+              <div className="flex-row">
+                <input
+                  type="text"
+                  value={row.model}
+                  onChange={(e) => updateRow(index, { model: e.target.value })}
+                  placeholder="gemini-2.5-flash または *"
+                  className="input-field flex-1"
+                  title="モデル名（* はその他すべて）"
+                />
+                <select
+                  value={row.safetyThreshold}
+                  onChange={(e) =>
+                    updateRow(index, { safetyThreshold: e.target.value })
+                  }
+                  className="input-field"
+                  title="安全性フィルタ"
+                >
+                  {SAFETY_THRESHOLDS.map((threshold) => (
+                    <option key={threshold.value} value={threshold.value}>
+                      {threshold.label}
+                    </option>
+                  ))}
+                </select>
+                <button
+                  onClick={() =>
+                    setRows((prev) => prev.filter((_, i) => i !== index))
+                  }
+                  className="save-button p-4-12 fs-12 min-w-auto"
+                >
+                  削除
+                </button>
+              </div>
+              <div className="flex-row mt-10">
+                <input
+                  type="number"
+                  step={0.1}
+                  value={row.temperature}
+                  onChange={(e) =>
+                    updateRow(index, { temperature: e.target.value })
+                  }
+                  placeholder="temperature (0)"
+                  className="input-field flex-1"
+                  title="temperature"
+                />
+                <input
+                  type="number"
+                  step={0.05}
+                  value={row.topP}
+                  onChange={(e) => updateRow(index, { topP: e.target.value })}
+                  placeholder="topP"
+                  className="input-field flex-1"
+                  title="topP"
+                />
+                <input
+                  type="number"
+                  value={row.maxOutputTokens}
+                  onChange={(e) =>
+                    updateRow(index, { maxOutputTokens: e.target.value })
+                  }
+                  placeholder="最大出力トークン"
+                  className="input-field flex-1"
+                  title="maxOutputTokens"
+                />
+                <input
+                  type="number"
+                  value={row.thinkingBudget}
+                  onChange={(e) =>
+                    updateRow(index, { thinkingBudget: e.target.value })
+                  }
+                  placeholder="思考トークン"
+                  className="input-field flex-1"
+                  title="thinkingBudget（0で無効、-1で自動）"
+                />
+              </div>
+            </div>
+          ))}
+          <div className="flex-row">
+            <button
+              onClick={() =>
+                setRows((prev) => [
+                  ...prev,
+                  {
+                    model: prev.length === 0 ? "*" : "",
+                    temperature: "",
+                    topP: "",
+                    maxOutputTokens: "",
+                    thinkingBudget: "",
+                    safetyThreshold: "",
+                  },
+                ])
+              }
+              className="save-button p-4-12 fs-12 min-w-auto"
+            >
+              追加
+            </button>
+            <button
+              onClick={handleSave}
+              className="save-button p-4-12 fs-12 min-w-auto"
+            >
+              保存
+            </button>
+          </div>
+        </div>
+      )}
+      <small>
+        モデルごとの temperature・topP・最大出力トークン・思考トークン・安全性フィルタを指定します。空欄は既定値（temperature 0）、「*」は個別設定のないモデルに適用されます
+      </small>
+    </div>
+  );
+}
@@ -0,0 +1,120 @@
+import { useState, useEffect } from "react";
+import { appStorage } from "../../utils/storage";
+
+export function GoogleCloudSection() {
+  const [apiKey, setApiKey] = useState("");
+  const [version, setVersion] = useState("v2");
+  const [projectId, setProjectId] = useState("");
+  const [location, setLocation] = useState("");
+  const [glossaryId, setGlossaryId] = useState("");
+  const [accessToken, setAccessToken] = useState("");
+  const [replaceWebEndpoint, setReplaceWebEndpoint] = useState(false);
+
+  useEffect(() => {
+    const config = appStorage.getProviderConfig("google-cloud");
+    const options = config.options || {};
+    setApiKey(config.apiKey || "");
+    setVersion(String(options.version ?? "v2"));
+    setProjectId(String(options.project_id ?? ""));
+    setLocation(String(options.location ?? ""));
+    setGlossaryId(String(options.glossary_id ?? ""));
+    setAccessToken(config.accessToken || "");
+    setReplaceWebEndpoint(appStorage.getProviderConfig("google").enabled === false);
+  }, []);
+
+  const handleSave = () => {
+    appStorage.setProviderConfig("google-cloud", {
+      apiKey: apiKey.trim(),
+      accessToken: accessToken.trim(),
+      options: {
+        version,
+        project_id: projectId.trim() || null,
+        location: location.trim() || null,
+        glossary_id: glossaryId.trim() || null,
+      },
+    });
+    // The official API can replace the free web endpoint entirely
+    appStorage.setProviderConfig("google", {
+      ...appStorage.getProviderConfig("google"),
+      enabled: !replaceWebEndpoint,
+    });
+    alert("Google Cloud Translationの設定を保存しました");
+  };
+
+  return (
+    <div className="form-group">
+      <label>Google Cloud Translation API キー (オプション)</label>
+      <input
+        type="password"
+        value={apiKey}
+        onChange={(e) => setApiKey(e.target.value)}
+        placeholder="Google Cloud APIキーを入力"
+        className="input-field"
+      />
+      <input
+        type="password"
+        value={accessToken}
+        onChange={(e) => setAccessToken(e.target.value)}
+        placeholder="OAuthアクセストークン（任意・APIキーの代わりに使用）"
+        className="input-field mt-10"
+      />
+      <div className="flex-row mt-10">
+        <select
+          value={version}
+          onChange={(e) => setVersion(e.target.value)}
+          className="select-field flex-1"
+          title="Cloud Translation API Version"
+        >
+          <option value="v2">v2 (Basic)</option>
+          <option value="v3">v3 (Advanced・用語集対応)</option>
+        </select>
+      </div>
+      {version === "v3" && (
+        <div className="flex-row mt-10">
+          <input
+            type="text"
+            value={projectId}
+            onChange={(e) => setProjectId(e.target.value)}
+            placeholder="プロジェクトID"
+            className="input-field flex-1"
+          />
+          <input
+            type="text"
+            value={location}
+            onChange={(e) => setLocation(e.target.value)}
+            placeholder="ロケーション（既定: global）"
+            className="input-field flex-1"
+          />
+          <input
+            type="text"
+            value={glossaryId}
+            onChange={(e) => setGlossaryId(e.target.value)}
+            placeholder="用語集ID（任意）"
+            className="input-field flex-1"
+          />
+        </div>
+      )}
+      <div className="flex-row mt-10">
+        <label className="flex-row-10 flex-1">
+          <input
+            type="checkbox"
+            checked={replaceWebEndpoint}
+            onChange={(e) => setReplaceWebEndpoint(e.target.checked)}
+          />
+          <span className="text-secondary">
+            無料のGoogle翻訳（Webエンドポイント）を使わない
+          </span>
+        </label>
+        <button
+          onClick={handleSave}
+          className="save-button p-8-16 fs-14 min-w-auto"
+        >
+          保存
+        </button>
+      </div>
+      <small>
+        公式APIはSLA付きでレート制限の影響を受けにくくなります。用語集を使う場合はリージョン（例: us-central1）を指定してください
+      </small>
+    </div>
+  );
+}
@@ -0,0 +1,90 @@
+import { useState, useEffect } from "react";
+import { invoke } from "@tauri-apps/api/tauri";
+import type { SupportedLanguage } from "../../types";
+import { appStorage } from "../../utils/storage";
+import { getProviderSettings } from "../../utils/providers";
+
+export function LibreTranslateSection() {
+  const [enabled, setEnabled] = useState(false);
+  const [apiKey, setApiKey] = useState("");
+  const [status, setStatus] = useState("");
+  const [statusError, setStatusError] = useState(false);
+  const [testing, setTesting] = useState(false);
+
+  useEffect(() => {
+    const config = appStorage.getProviderConfig("libretranslate");
+    setEnabled(config.enabled ?? false);
+    setApiKey(config.apiKey || "");
+  }, []);
+
+  const saveConfig = (nextEnabled: boolean, nextApiKey: string) => {
+    appStorage.setProviderConfig("libretranslate", {
+      enabled: nextEnabled,
+      apiKey: nextApiKey.trim(),
+    });
+  };
+
+  const handleToggle = () => {
+    const next = !enabled;
+    setEnabled(next);
+    saveConfig(next, apiKey);
+  };
+
+  const handleTest = async () => {
+    saveConfig(enabled, apiKey);
+    setTesting(true);
+    setStatus("");
+    setStatusError(false);
+    try {
+      const languages = await invoke<SupportedLanguage[]>("list_languages", {
+        providerId: "libretranslate",
+        settings: getProviderSettings("libretranslate"),
+      });
+      setStatus(`接続成功: ${languages.length}言語に対応しています`);
+    } catch (error) {
+      setStatus(`接続失敗: ${error}`);
+      setStatusError(true);
+    } finally {
+      setTesting(false);
+    }
+  };
+
+  return (
+    <div className="form-group">
+      <label>LibreTranslate (セルフホスト)</label>
+      <div className="flex-row-10">
+        <label className="switch">
+          <input
+            type="checkbox"
+            checked={enabled}
+            onChange={handleToggle}
+            aria-label="LibreTranslateを有効化"
+          />
+          <span className="slider"></span>
+        </label>
+        <span className="text-secondary">{enabled ? "有効" : "無効"}</span>
+      </div>
+      <div className="flex-row mt-10">
+        <input
+          type="password"
+          value={apiKey}
+          onChange={(e) => setApiKey(e.target.value)}
+          onBlur={() => saveConfig(enabled, apiKey)}
+          placeholder="APIキー（サーバーが要求する場合のみ）"
+          className="input-field flex-1"
+        />
+        <button
+          onClick={handleTest}
+          disabled={testing}
+          className="save-button p-8-16 fs-14 min-w-auto"
+        >
+          {testing ? "確認中..." : "接続テスト"}
+        </button>
+      </div>
+      <small className={statusError ? "error-text" : ""}>
+        {status ||
+          "サーバーのURLは「エンドポイント」で設定してください（デフォルト: http://localhost:5000）"}
+      </small>
+    </div>
+  );
+}
@@ -0,0 +1,141 @@
+import { useState, useEffect } from "react";
+import type { NetworkSettings, ProxyMode } from "../../types";
+import { appStorage } from "../../utils/storage";
+import { applyNetworkSettings } from "../../utils/network";
+
+export function NetworkSection() {
+  const [showNetwork, setShowNetwork] = useState(false);
+  const [proxyMode, setProxyMode] = useState<ProxyMode>("system");
+  const [proxyUrl, setProxyUrl] = useState("");
+  const [proxyUsername, setProxyUsername] = useState("");
+  const [proxyPassword, setProxyPassword] = useState("");
+  const [noProxy, setNoProxy] = useState("");
+  // One PEM file path per line
+  const [caCertPaths, setCaCertPaths] = useState("");
+
+  useEffect(() => {
+    const saved = appStorage.getNetworkSettings();
+    setProxyMode(saved.proxy_mode);
+    setProxyUrl(saved.proxy_url || "");
+    setProxyUsername(saved.proxy_username || "");
+    setProxyPassword(saved.proxy_password || "");
+    setNoProxy(saved.no_proxy || "");
+    setCaCertPaths(saved.ca_cert_paths.join("\n"));
+  }, []);
+
+  const handleSave = async () => {
+    const settings: NetworkSettings = {
+      proxy_mode: proxyMode,
+      proxy_url: proxyUrl.trim() || null,
+      proxy_username: proxyUsername.trim() || null,
+      proxy_password: proxyPassword || null,
+      no_proxy: noProxy.trim() || null,
+      ca_cert_paths: caCertPaths
+        .split("\n")
+        .map((path) => path.trim())
+        .filter((path) => path),
+    };
+    try {
+      // Validate in the backend first so a broken proxy URL or certificate is never stored
+      await applyNetworkSettings(settings);
+      appStorage.setNetworkSettings(settings);
+      alert("ネットワーク設定を保存しました");
+    } catch (err) {
+      alert(`ネットワーク設定を適用できません: ${err}`);
+    }
+  };
+
+  return (
+    <div className="form-group">
+      <div className="flex-between">
+        <label>プロキシ・証明書 (上級者向け)</label>
+        <button
+          onClick={() => setShowNetwork(!showNetwork)}
+          className="save-button p-4-12 fs-12 min-w-auto"
+        >
+          {showNetwork ? "非表示" : "表示"}
+        </button>
+      </div>
+      {showNetwork && (
+        <div className="mt-10">
+          <div className="mb-10">
+            <small className="fw-500">プロキシ</small>
+            <select
+              value={proxyMode}
+              onChange={(e) => setProxyMode(e.target.value as ProxyMode)}
+              className="input-field"
+            >
+              <option value="system">システム設定・環境変数を使用</option>
+              <option value="none">使用しない</option>
+              <option value="manual">手動設定</option>
+            </select>
+            {proxyMode === "system" && (
+              <small className="color-999">
+                HTTP_PROXY / HTTPS_PROXY / ALL_PROXY / NO_PROXY を自動的に使用します
+              </small>
+            )}
+          </div>
+          {proxyMode === "manual" && (
+            <>
+              <div className="mb-10">
+                <small className="fw-500">プロキシURL</small>
+                <input
+                  type="text"
+                  value={proxyUrl}
+                  onChange={(e) => setProxyUrl(e.target.value)}
+                  placeholder="http://proxy.example.com:8080 / socks5://127.0.0.1:1080"
+                  className="input-field"
+                />
+              </div>
+              <div className="flex-row mb-10">
+                <input
+                  type="text"
+                  value={proxyUsername}
+                  onChange={(e) => setProxyUsername(e.target.value)}
+                  placeholder="ユーザー名 (任意)"
+                  className="input-field flex-1"
+                />
+                <input
+                  type="password"
+                  value={proxyPassword}
+                  onChange={(e) => setProxyPassword(e.target.value)}
+                  placeholder="パスワード (任意)"
+                  className="input-field flex-1"
+                />
+              </div>
+              <div className="mb-10">
+                <small className="fw-500">プロキシを使わないホスト</small>
+                <input
+                  type="text"
+                  value={noProxy}
+                  onChange={(e) => setNoProxy(e.target.value)}
+                  placeholder="localhost, 127.0.0.1, .corp.example.com, 10.0.0.0/8"
+                  className="input-field"
+                />
+              </div>
+            </>
+          )}
+          <div className="mb-10">
+            <small className="fw-500">追加のルート証明書 (PEM、1行に1ファイル)</small>
+            <textarea
+              value={caCertPaths}
+              onChange={(e) => setCaCertPaths(e.target.value)}
+              placeholder="C:\certs\corporate-root.pem"
+              className="input-field"
+              rows={2}
+            />
+          </div>
+          <button
+            onClick={handleSave}
+            className="save-button p-4-12 fs-12 min-w-auto"
+          >
+            保存
+          </button>
+        </div>
+      )}
+      <small>
+        すべてのプロバイダーの通信に適用されます（社内プロキシやSSL検査用の証明書など）
+      </small>
+    </div>
+  );
+}
@@ -0,0 +1,108 @@
+import { useState, useEffect } from "react";
+import { invoke } from "@tauri-apps/api/tauri";
+import type { ProviderInfo } from "../../types";
+import { appStorage } from "../../utils/storage";
+import { getProviderSettings } from "../../utils/providers";
+
+export function OfflineSection() {
+  // Only builds with the offline-nmt feature register the provider
+  const [available, setAvailable] = useState(false);
+  const [enabled, setEnabled] = useState(false);
+  const [pairs, setPairs] = useState<string[]>([]);
+  // Pair used when the source language cannot be detected ("" = the only pair into the target)
+  const [fallbackPair, setFallbackPair] = useState("");
+  const [status, setStatus] = useState("");
+
+  const loadPairs = async () => {
+    try {
+      const installed = await invoke<string[]>("list_models", {
+        providerId: "offline",
+        settings: getProviderSettings("offline"),
+      });
+      setPairs(installed);
+      setStatus("");
+    } catch (error) {
+      setStatus(`モデル一覧の取得に失敗しました: ${error}`);
+    }
+  };
+
+  useEffect(() => {
+    const config = appStorage.getProviderConfig("offline");
+    setEnabled(config.enabled ?? false);
+    setFallbackPair(config.model ?? "");
+    invoke<ProviderInfo[]>("list_providers")
+      .then((providers) => {
+        if (providers.some((p) => p.id === "offline")) {
+          setAvailable(true);
+          loadPairs();
+        }
+      })
+      .catch((err) => console.error("Failed to load providers:", err));
+  }, []);
+
+  const handleToggle = () => {
+    const next = !enabled;
+    setEnabled(next);
+    appStorage.setProviderConfig("offline", {
+      ...appStorage.getProviderConfig("offline"),
+      enabled: next,
+    });
+  };
+
+  const handlePairChange = (pair: string) => {
+    setFallbackPair(pair);
+    appStorage.setProviderConfig("offline", {
+      ...appStorage.getProviderConfig("offline"),
+      model: pair || undefined,
+    });
+  };
+
+  if (!available) {
+    return null;
+  }
+
+  return (
+    <div className="form-group">
+      <label>オフライン翻訳 (Marian / opus-mt)</label>
+      <div className="flex-row-10">
+        <label className="switch">
+          <input
+            type="checkbox"
+            checked={enabled}
+            onChange={handleToggle}
+            aria-label="オフライン翻訳を有効化"
+          />
+          <span className="slider"></span>
+        </label>
+        <span className="text-secondary">{enabled ? "有効" : "無効"}</span>
+        <button
+          onClick={loadPairs}
+          className="save-button p-4-12 fs-12 min-w-auto"
+        >
+          再読み込み
+        </button>
+      </div>
+      {pairs.length > 1 && (
+        <select
+          value={fallbackPair}
+          onChange={(e) => handlePairChange(e.target.value)}
+          className="select-field mt-10"
+          title="元言語を検出できないときに使うモデル"
+        >
+          <option value="">元言語が不明なとき: 自動</option>
+          {pairs.map((pair) => (
+            <option key={pair} value={pair}>
+              元言語が不明なとき: {pair}
+            </option>
+          ))}
+        </select>
+      )}
+      <small className={status ? "error-text" : ""}>
+        {status ||
+          (pairs.length > 0
+            ? `インストール済み: ${pairs.join(", ")}`
+            : "モデルがインストールされていません。%APPDATA%\\com.afterpot.app\\models\\<元言語>-<翻訳先> に配置してください")}
+      </small>
+    </div>
+  );
+}
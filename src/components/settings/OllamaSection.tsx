@@ -0,0 +1,79 @@
+import { useState, useEffect } from "react";
+import { invoke } from "@tauri-apps/api/tauri";
+import { appStorage } from "../../utils/storage";
+import { getProviderSettings } from "../../utils/providers";
+
+export function OllamaSection() {
+  const [model, setModel] = useState("");
+  const [availableModels, setAvailableModels] = useState<string[]>([]);
+  const [isLoadingModels, setIsLoadingModels] = useState(false);
+  const [modelError, setModelError] = useState("");
+
+  useEffect(() => {
+    setModel(appStorage.getProviderConfig("ollama").model || "");
+  }, []);
+
+  const fetchModels = async () => {
+    setIsLoadingModels(true);
+    setModelError("");
+    try {
+      const models = await invoke<string[]>("list_models", {
+        providerId: "ollama",
+        settings: getProviderSettings("ollama"),
+      });
+      setAvailableModels(models);
+      if (models.length === 0) {
+        setModelError("モデルがありません（`ollama pull <モデル名>` で取得してください）");
+      }
+    } catch (error) {
+      console.error("Failed to fetch Ollama models:", error);
+      setModelError(`エラー: ${error}`);
+    } finally {
+      setIsLoadingModels(false);
+    }
+  };
+
+  const handleModelChange = (newModel: string) => {
+    setModel(newModel);
+    appStorage.setProviderConfig("ollama", { model: newModel });
+  };
+
+  return (
+    <div className="form-group">
+      <label>Ollama モデル (オプション・ローカル翻訳)</label>
+      <div className="flex-row">
+        <select
+          value={model}
+          onChange={(e) => handleModelChange(e.target.value)}
+          className="select-field flex-1"
+          title="Ollama Model"
+          disabled={isLoadingModels}
+        >
+          <option value="">使用しない</option>
+          {model && !availableModels.includes(model) && (
+            <option value={model}>{model}</option>
+          )}
+          {availableModels.map((m) => (
+            <option key={m} value={m}>
+              {m}
+            </option>
+          ))}
+        </select>
+        <button
+          onClick={fetchModels}
+          disabled={isLoadingModels}
+          className="save-button p-8-16 fs-14 min-w-auto"
+        >
+          {isLoadingModels ? "取得中..." : "モデル取得"}
+        </button>
+      </div>
+      <small className={modelError ? "error-text" : ""}>
+        {isLoadingModels && "ローカルのモデル一覧を取得中..."}
+        {!isLoadingModels && modelError}
+        {!isLoadingModels &&
+          !modelError &&
+          "モデルを選択するとテキストを外部に送信せず端末上で翻訳します"}
+      </small>
+    </div>
+  );
+}
@@ -0,0 +1,52 @@
+import { useState, useEffect } from "react";
+import { appStorage } from "../../utils/storage";
+
+export function OpenAiSection() {
+  const [apiKey, setApiKey] = useState("");
+  const [model, setModel] = useState("");
+
+  useEffect(() => {
+    const config = appStorage.getProviderConfig("openai");
+    setApiKey(config.apiKey || "");
+    setModel(config.model || "");
+  }, []);
+
+  const handleSave = () => {
+    appStorage.setProviderConfig("openai", {
+      apiKey: apiKey.trim(),
+      model: model.trim(),
+    });
+    alert("OpenAI互換プロバイダーの設定を保存しました");
+  };
+
+  return (
+    <div className="form-group">
+      <label>OpenAI互換 API (オプション)</label>
+      <input
+        type="password"
+        value={apiKey}
+        onChange={(e) => setApiKey(e.target.value)}
+        placeholder="APIキー（ローカルサーバーの場合は空欄可）"
+        className="input-field"
+      />
+      <div className="flex-row mt-10">
+        <input
+          type="text"
+          value={model}
+          onChange={(e) => setModel(e.target.value)}
+          placeholder="モデル名（例: gpt-4o-mini, llama-3.1-8b-instruct）"
+          className="input-field flex-1"
+        />
+        <button
+          onClick={handleSave}
+          className="save-button p-8-16 fs-14 min-w-auto"
+        >
+          保存
+        </button>
+      </div>
+      <small>
+        モデル名を入力すると有効化されます。ベースURL（OpenRouter、llama.cpp、vLLM、LM Studioなど）は「エンドポイント」で設定してください
+      </small>
+    </div>
+  );
+}
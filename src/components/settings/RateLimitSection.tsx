@@ -0,0 +1,128 @@
+import { useState, useEffect } from "react";
+import { invoke } from "@tauri-apps/api/tauri";
+import type { ProviderInfo, RateLimitConfig } from "../../types";
+import { appStorage } from "../../utils/storage";
+import { applyRateLimits } from "../../utils/network";
+
+// Shown for providers without a saved entry (mirrors the backend defaults)
+const DEFAULT_LIMITS: Record<string, RateLimitConfig> = {
+  google: { requests_per_minute: 30, max_concurrent: 2, on_limit: "queue" },
+};
+const UNLIMITED: RateLimitConfig = {
+  requests_per_minute: 0,
+  max_concurrent: 0,
+  on_limit: "queue",
+};
+
+export function RateLimitSection() {
+  const [showLimits, setShowLimits] = useState(false);
+  const [providers, setProviders] = useState<ProviderInfo[]>([]);
+  const [limits, setLimits] = useState<Record<string, RateLimitConfig>>({});
+
+  useEffect(() => {
+    setLimits(appStorage.getRateLimits());
+    invoke<ProviderInfo[]>("list_providers")
+      .then(setProviders)
+      .catch((err) => console.error("Failed to load providers:", err));
+  }, []);
+
+  const limitFor = (providerId: string): RateLimitConfig =>
+    limits[providerId] ?? DEFAULT_LIMITS[providerId] ?? UNLIMITED;
+
+  const updateLimit = (providerId: string, patch: Partial<RateLimitConfig>) => {
+    setLimits((prev) => ({
+      ...prev,
+      [providerId]: { ...limitFor(providerId), ...patch },
+    }));
+  };
+
+  const parseCount = (value: string) =>
+    Math.max(0, Math.floor(Number(value) || 0));
+
+  const handleSave = async () => {
+    try {
+      await applyRateLimits(limits);
+      appStorage.setRateLimits(limits);
+      alert("レート制限を保存しました");
+    } catch (err) {
+      alert(`レート制限を適用できません: ${err}`);
+    }
+  };
+
+  return (
+    <div className="form-group">
+      <div className="flex-between">
+        <label>レート制限 (上級者向け)</label>
+        <button
+          onClick={() => setShowLimits(!showLimits)}
+          className="save-button p-4-12 fs-12 min-w-auto"
+        >
+          {showLimits ? "非表示" : "表示"}
+        </button>
+      </div>
+      {showLimits && (
+        <div className="mt-10">
+          {providers.map((provider) => {
+            const limit = limitFor(provider.id);
+            return (
+              <div key={provider.id} className="mb-10">
+                <small className="fw-500">{provider.display_name}</small>
+                <div className="flex-row">
+                  <input
+                    type="number"
+                    min={0}
+                    value={limit.requests_per_minute}
+                    onChange={(e) =>
+                      updateLimit(provider.id, {
+                        requests_per_minute: parseCount(e.target.value),
+                      })
+                    }
+                    className="input-field flex-1"
+                    title="1分あたりのリクエスト数 (0 = 無制限)"
+                  />
+                  <input
+                    type="number"
+                    min={0}
+                    value={limit.max_concurrent}
+                    onChange={(e) =>
+                      updateLimit(provider.id, {
+                        max_concurrent: parseCount(e.target.value),
+                      })
+                    }
+                    className="input-field flex-1"
+                    title="同時リクエスト数 (0 = 無制限)"
+                  />
+                  <select
+                    value={limit.on_limit}
+                    onChange={(e) =>
+                      updateLimit(provider.id, {
+                        on_limit: e.target.value as RateLimitConfig["on_limit"],
+                      })
+                    }
+                    className="input-field"
+                    title="上限を超えたとき"
+                  >
+                    <option value="queue">待機</option>
+                    <option value="reject">送信しない</option>
+                  </select>
+                </div>
+              </div>
+            );
+          })}
+          <button
+            onClick={handleSave}
+            className="save-button p-4-12 fs-12 min-w-auto"
+          >
+            保存
+          </button>
+          <small className="color-999">
+            左から: 1分あたりのリクエスト数 / 同時リクエスト数（0 = 無制限）/ 上限を超えたときの動作
+          </small>
+        </div>
+      )}
+      <small>
+        送信前にアプリ内で回数を制限し、Google翻訳（Web）の429エラーやCAPTCHAを防ぎます
+      </small>
+    </div>
+  );
+}
@@ -0,0 +1,186 @@
+import { useState, useEffect } from "react";
+import { invoke } from "@tauri-apps/api/tauri";
+import type { DirectionRule, ProviderInfo } from "../../types";
+import { appStorage } from "../../utils/storage";
+import { applyRoutingRules } from "../../utils/network";
+
+const SOURCE_LANGUAGES = ["*", "ja", "en", "zh", "ko", "fr", "de", "es"];
+const TARGET_LANGUAGES = SOURCE_LANGUAGES.filter((lang) => lang !== "*");
+
+// Editable row: providers are typed as a comma-separated id list
+interface RuleRow {
+  source: string;
+  target: string;
+  providers: string;
+}
+
+export function RoutingSection() {
+  const [showRules, setShowRules] = useState(false);
+  const [providers, setProviders] = useState<ProviderInfo[]>([]);
+  const [rows, setRows] = useState<RuleRow[]>([]);
+  const [reverseTarget, setReverseTarget] = useState("en");
+  const [reverseWhenSame, setReverseWhenSame] = useState(true);
+
+  useEffect(() => {
+    const saved = appStorage.getRoutingSettings();
+    setReverseTarget(saved.reverse_target);
+    setReverseWhenSame(saved.reverse_when_same);
+    setRows(
+      saved.rules.map((rule) => ({
+        source: rule.source,
+        target: rule.target,
+        providers: rule.providers.join(", "),
+      }))
+    );
+    invoke<ProviderInfo[]>("list_providers")
+      .then(setProviders)
+      .catch((err) => console.error("Failed to load providers:", err));
+  }, []);
+
+  const updateRow = (index: number, patch: Partial<RuleRow>) => {
+    setRows((prev) =>
+      prev.map((row, i) => (i === index ? { ...row, ...patch } : row))
+    );
+  };
+
+  const handleSave = async () => {
+    const knownIds = new Set(providers.map((p) => p.id));
+    const rules: DirectionRule[] = [];
+    for (const row of rows) {
+      const ids = row.providers
+        .split(/[,\s]+/)
+        .map((id) => id.trim())
+        .filter((id) => id);
+      const unknown = ids.filter((id) => !knownIds.has(id));
+      if (unknown.length > 0) {
+        alert(`不明なプロバイダーIDです: ${unknown.join(", ")}`);
+        return;
+      }
+      rules.push({ source: row.source, target: row.target, providers: ids });
+    }
+
+    const settings = {
+      reverse_target: reverseTarget,
+      reverse_when_same: reverseWhenSame,
+      rules,
+    };
+    try {
+      await applyRoutingRules(settings);
+      appStorage.setRoutingSettings(settings);
+      alert("翻訳方向のルールを保存しました");
+    } catch (err) {
+      alert(`翻訳方向のルールを適用できません: ${err}`);
+    }
+  };
+
+  return (
+    <div className="form-group">
+      <div className="flex-between">
+        <label>翻訳方向のルール (上級者向け)</label>
+        <button
+          onClick={() => setShowRules(!showRules)}
+          className="save-button p-4-12 fs-12 min-w-auto"
+        >
+          {showRules ? "非表示" : "表示"}
+        </button>
+      </div>
+      {showRules && (
+        <div className="mt-10">
+          <div className="flex-row mb-10">
+            <label className="fs-12">
+              <input
+                type="checkbox"
+                checked={reverseWhenSame}
+                onChange={(e) => setReverseWhenSame(e.target.checked)}
+              />{" "}
+              翻訳先と同じ言語の場合は
+            </label>
+            <select
+              value={reverseTarget}
+              onChange={(e) => setReverseTarget(e.target.value)}
+              className="input-field"
+              disabled={!reverseWhenSame}
+            >
+              {TARGET_LANGUAGES.map((lang) => (
+                <option key={lang} value={lang}>
+                  {lang}
+                </option>
+              ))}
+            </select>
+            <span className="fs-12">へ翻訳</span>
+          </div>
+          {rows.map((row, index) => (
+            <div key={index} className="flex-row mb-10">
+              <select
+                value={row.source}
+                onChange={(e) => updateRow(index, { source: e.target.value })}
+                className="input-field"
+                title="検出された言語"
+              >
+                {SOURCE_LANGUAGES.map((lang) => (
+                  <option key={lang} value={lang}>
+                    {lang === "*" ? "その他" : lang}
+                  </option>
+                ))}
+              </select>
+              <select
+                value={row.target}
+                onChange={(e) => updateRow(index, { target: e.target.value })}
+                className="input-field"
+                title="翻訳先"
+              >
+                {TARGET_LANGUAGES.map((lang) => (
+                  <option key={lang} value={lang}>
+                    {lang}
+                  </option>
+                ))}
+              </select>
+              <input
+                type="text"
+                value={row.providers}
+                onChange={(e) =>
+                  updateRow(index, { providers: e.target.value })
+                }
+                placeholder="優先プロバイダー (空欄ですべて)"
+                className="input-field flex-1"
+              />
+              <button
+                onClick={() =>
+                  setRows((prev) => prev.filter((_, i) => i !== index))
+                }
+                className="save-button p-4-12 fs-12 min-w-auto"
+              >
+                削除
+              </button>
+            </div>
+          ))}
+          <div className="flex-row">
+            <button
+              onClick={() =>
+                setRows((prev) => [
+                  ...prev,
+                  { source: "en", target: "ja", providers: "" },
+                ])
+              }
+              className="save-button p-4-12 fs-12 min-w-auto"
+            >
+              追加
+            </button>
+            <button
+              onClick={handleSave}
+              className="save-button p-4-12 fs-12 min-w-auto"
+            >
+              保存
+            </button>
+          </div>
+          <small className="color-999">
+            利用可能なID: {providers.map((p) => p.id).join(", ")}
+          </small>
+        </div>
+      )}
+      <small>
+        検出された言語ごとに翻訳先と優先するプロバイダーを指定します。ルールに該当しない場合は「翻訳先言語」へ翻訳します
+      </small>
+    </div>
+  );
+}
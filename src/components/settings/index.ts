@@ -3,3 +3,18 @@ export { ThemeSection } from "./ThemeSection";
 export { HistorySection } from "./HistorySection";
 export { ErrorLogSection } from "./ErrorLogSection";
 export { AutoStartSection } from "./AutoStartSection";
+export { EndpointSection } from "./EndpointSection";
+export { DeeplSection } from "./DeeplSection";
+export { OpenAiSection } from "./OpenAiSection";
+export { OllamaSection } from "./OllamaSection";
+export { LibreTranslateSection } from "./LibreTranslateSection";
+export { AzureSection } from "./AzureSection";
+export { GoogleCloudSection } from "./GoogleCloudSection";
+export { GeminiGenerationSection } from "./GeminiGenerationSection";
+export { AnthropicSection } from "./AnthropicSection";
+export { OfflineSection } from "./OfflineSection";
+export { FallbackSection } from "./FallbackSection";
+export { RoutingSection } from "./RoutingSection";
+export { NetworkSection } from "./NetworkSection";
+export { RateLimitSection } from "./RateLimitSection";
+export { CacheSection } from "./CacheSection";
@@ -1,13 +1,229 @@
 
 // Translation related types
 export interface TranslationResult {
+    providerId: string;
     originalText: string;
     translatedText: string;
     detectedLanguage: string;
+    // Set when the provider reported its own detection result
+    detectionConfidence?: number;
+    // Set when the request needed retries
+    attempts?: number;
+    // Providers of a fallback chain that failed before this result
+    fallbackFrom?: string[];
+    // Served from the translation cache
+    cached?: boolean;
+    // Dictionary data for single words / short phrases
+    dictionary?: DictionaryEntry;
+    // Time the backend command took
+    latencyMs?: number;
     targetLanguage: string;
     translationService: string;
 }
 
+// Translation provider types (mirrors src-tauri/src/providers)
+export interface ProviderCapabilities {
+    requires_api_key: boolean;
+    // An access token (ProviderSettings.access_token) can stand in for the API key
+    accepts_access_token: boolean;
+    requires_model: boolean;
+    enabled_by_default: boolean;
+    supports_model_selection: boolean;
+    supports_auto_detect: boolean;
+    is_llm: boolean;
+}
+
+export interface ProviderInfo {
+    id: string;
+    display_name: string;
+    capabilities: ProviderCapabilities;
+    default_base_url: string;
+    base_url_env: string;
+}
+
+export interface ProviderSettings {
+    api_key?: string | null;
+    access_token?: string | null;
+    model?: string | null;
+    base_url?: string | null;
+    options?: Record<string, unknown>;
+}
+
+// Ordered providers tried for a language pair until one succeeds ("*" matches any language)
+export interface FallbackChain {
+    source: string;
+    target: string;
+    providers: string[];
+}
+
+// Gemini generation settings for one model (backend: providers::gemini::GenerationSettings);
+// stored in the "gemini" provider config as options.models[model name or "*"]
+export interface GeminiGenerationSettings {
+    temperature?: number;
+    top_p?: number;
+    max_output_tokens?: number;
+    // 0 disables thinking, -1 lets the model decide
+    thinking_budget?: number;
+    safety_threshold?: string;
+}
+
+// Direction rule (backend: providers::routing::DirectionRule); source "*" matches any language
+export interface DirectionRule {
+    source: string;
+    target: string;
+    // Preferred provider ids for the pair; empty = every enabled provider
+    providers: string[];
+}
+
+// Saved routing settings; the default target is the "翻訳先言語" setting
+export interface RoutingSettings {
+    reverse_target: string;
+    reverse_when_same: boolean;
+    rules: DirectionRule[];
+}
+
+// Result of the resolve_direction command
+export interface ResolvedDirection {
+    source_lang: string;
+    target_lang: string;
+    detected_language: string | null;
+    providers: string[];
+    rule_index: number | null;
+    reversed: boolean;
+    // detected_language is only a local guess (network detection was unavailable)
+    low_confidence: boolean;
+}
+
+// Proxy / certificate settings applied to every provider (backend: providers::http::NetworkSettings)
+export type ProxyMode = "system" | "none" | "manual";
+
+export interface NetworkSettings {
+    proxy_mode: ProxyMode;
+    proxy_url?: string | null;
+    proxy_username?: string | null;
+    proxy_password?: string | null;
+    no_proxy?: string | null;
+    ca_cert_paths: string[];
+}
+
+// Client-side rate limit per provider (backend: providers::rate_limit::RateLimitConfig); 0 = unlimited
+export interface RateLimitConfig {
+    requests_per_minute: number;
+    max_concurrent: number;
+    on_limit: "queue" | "reject";
+}
+
+// Error rejected by translation commands
+export type TranslateError =
+    | {
+          kind: "rate_limited";
+          provider_id: string;
+          retry_after_ms: number | null;
+          message: string;
+      }
+    | { kind: "failed"; message: string };
+
+// Translation cache (backend: providers::cache)
+export interface CacheStats {
+    memory_entries: number;
+    disk_entries: number;
+    disk_bytes: number;
+    hits: number;
+    misses: number;
+}
+
+export interface CacheEntryInfo {
+    provider_id: string;
+    model: string;
+    source_lang: string;
+    target_lang: string;
+    text_preview: string;
+    translation_preview: string;
+    // Unix seconds
+    created_at: number;
+}
+
+// Stored per-provider configuration (see appStorage.getProviderConfig)
+export interface ProviderConfig {
+    enabled?: boolean;
+    apiKey?: string;
+    accessToken?: string;
+    model?: string;
+    options?: Record<string, unknown>;
+}
+
+export interface DetectedLanguage {
+    language: string;
+    confidence: number;
+}
+
+export interface SupportedLanguage {
+    code: string;
+    name: string;
+}
+
+export interface TranslationOutcome {
+    provider_id: string;
+    provider_name: string;
+    translated_text: string;
+    model_used: string | null;
+    detected_language: DetectedLanguage | null;
+    // HTTP attempts the request took (1 = no retries)
+    attempts: number;
+    // Providers of a fallback chain that failed before this one answered
+    fallback_errors: ProviderFailure[];
+    // Served from the translation cache without contacting the provider
+    cached: boolean;
+    dictionary: DictionaryEntry | null;
+    // Source / translation pairs per sentence (empty when the provider does not report them)
+    segments: TranslatedSegment[];
+    // Wall-clock time of the command, including retries and fallbacks
+    latency_ms: number;
+}
+
+export interface TranslatedSegment {
+    source: string;
+    translation: string;
+}
+
+// Structured dictionary result (currently from Google for single words / short phrases)
+export interface DictionaryEntry {
+    word: string;
+    source_transliteration: string | null;
+    transliteration: string | null;
+    // Alternative translations grouped by part of speech
+    senses: DictionarySense[];
+    // Synonyms of the headword (source language)
+    synonyms: DictionarySense[];
+    examples: string[];
+}
+
+export interface DictionarySense {
+    part_of_speech: string;
+    terms: DictionaryTerm[];
+}
+
+export interface DictionaryTerm {
+    text: string;
+    reverse_translations: string[];
+    score: number | null;
+}
+
+export interface ProviderFailure {
+    provider_id: string;
+    provider_name: string;
+    error: string;
+}
+
+// Payload of the `translation-chunk` event sent while a provider streams its output
+export interface TranslationChunk {
+    request_id: string;
+    provider_id: string;
+    delta: string;
+    // Full translation received so far
+    text: string;
+}
+
 // History related types
 export interface HistoryEntry {
     id: string;
@@ -0,0 +1,47 @@
+import { invoke } from "@tauri-apps/api/tauri";
+import type {
+    NetworkSettings,
+    RateLimitConfig,
+    RoutingSettings,
+    TranslateError,
+} from "../types";
+import { appStorage } from "./storage";
+
+// Push proxy / certificate settings to the backend's shared HTTP client.
+// The backend keeps no settings of its own, so this runs on startup and after every save.
+export async function applyNetworkSettings(
+    settings: NetworkSettings = appStorage.getNetworkSettings()
+): Promise<void> {
+    await invoke("set_network_settings", { settings });
+}
+
+// Push the per-provider rate limits to the backend (same lifecycle as the network settings)
+export async function applyRateLimits(
+    limits: Record<string, RateLimitConfig> = appStorage.getRateLimits()
+): Promise<void> {
+    await invoke("set_rate_limits", { limits });
+}
+
+// Push the language-pair routing rules; the default target comes from the target language setting
+export async function applyRoutingRules(
+    settings: RoutingSettings = appStorage.getRoutingSettings()
+): Promise<void> {
+    await invoke("set_routing_rules", {
+        rules: { ...settings, default_target: appStorage.getTargetLanguage() },
+    });
+}
+
+// Turn the on-disk translation cache on or off (turning it off deletes the stored entries)
+export async function applyCachePersistence(
+    enabled: boolean = appStorage.getCachePersist()
+): Promise<void> {
+    await invoke("set_cache_persistence", { enabled });
+}
+
+// Translation commands reject with a TranslateError; other commands reject with a string
+export function describeError(error: unknown): string {
+    if (error && typeof error === "object" && "message" in error) {
+        return String((error as TranslateError).message);
+    }
+    return String(error);
+}
@@ -0,0 +1,82 @@
+import type { FallbackChain, ProviderInfo, ProviderSettings } from "../types";
+import { appStorage } from "./storage";
+
+// Build the per-call settings passed to the backend `translate` command
+export function getProviderSettings(providerId: string): ProviderSettings {
+    const base_url = appStorage.getProviderBaseUrl(providerId) || null;
+    switch (providerId) {
+        case "gemini": {
+            const model = appStorage.getGeminiModel();
+            return {
+                api_key: appStorage.getGeminiApiKey() || null,
+                model: model === "auto" ? null : model,
+                base_url,
+                options: appStorage.getProviderConfig("gemini").options || {},
+            };
+        }
+        default: {
+            const config = appStorage.getProviderConfig(providerId);
+            return {
+                api_key: config.apiKey || null,
+                access_token: config.accessToken || null,
+                model: config.model || null,
+                base_url,
+                options: config.options || {},
+            };
+        }
+    }
+}
+
+// A provider is usable once it is switched on and its required credentials are configured
+export function isProviderEnabled(
+    provider: ProviderInfo,
+    settings: ProviderSettings
+): boolean {
+    const enabled =
+        appStorage.getProviderConfig(provider.id).enabled ??
+        provider.capabilities.enabled_by_default;
+    if (!enabled) {
+        return false;
+    }
+    const hasCredential =
+        !!settings.api_key ||
+        (provider.capabilities.accepts_access_token && !!settings.access_token);
+    if (provider.capabilities.requires_api_key && !hasCredential) {
+        return false;
+    }
+    if (provider.capabilities.requires_model && !settings.model) {
+        return false;
+    }
+    return true;
+}
+
+// Most specific configured chain for a language pair (exact pair > one wildcard > "*" → "*")
+export function getFallbackChain(
+    source: string,
+    target: string
+): FallbackChain | null {
+    const matches = (pattern: string, lang: string) =>
+        pattern === "*" || pattern === lang;
+    const specificity = (chain: FallbackChain) =>
+        (chain.source === "*" ? 0 : 2) + (chain.target === "*" ? 0 : 1);
+
+    let best: FallbackChain | null = null;
+    for (const chain of appStorage.getFallbackChains()) {
+        if (chain.providers.length === 0) continue;
+        if (!matches(chain.source, source) || !matches(chain.target, target)) {
+            continue;
+        }
+        if (best === null || specificity(chain) > specificity(best)) {
+            best = chain;
+        }
+    }
+    return best;
+}
+
+// Label shown in the popup, e.g. "Gemini (gemini-2.0-flash)"
+export function formatServiceName(
+    providerName: string,
+    modelUsed: string | null
+): string {
+    return modelUsed ? `${providerName} (${modelUsed})` : providerName;
+}
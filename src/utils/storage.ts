@@ -1,4 +1,14 @@
-import type { HistoryEntry, ErrorLog, WindowState, Theme } from "../types";
+import type {
+    HistoryEntry,
+    ErrorLog,
+    WindowState,
+    Theme,
+    ProviderConfig,
+    FallbackChain,
+    NetworkSettings,
+    RateLimitConfig,
+    RoutingSettings,
+} from "../types";
 
 // Caps to prevent unbounded localStorage growth
 const MAX_HISTORY_ENTRIES = 100;
@@ -16,6 +26,13 @@ const STORAGE_KEYS = {
     SETTINGS_WINDOW_STATE: "settingsWindowState",
     TRANSLATION_HISTORY: "translationHistory",
     ERROR_LOGS: "errorLogs",
+    PROVIDER_BASE_URLS: "providerBaseUrls",
+    PROVIDER_CONFIGS: "providerConfigs",
+    FALLBACK_CHAINS: "fallbackChains",
+    NETWORK_SETTINGS: "networkSettings",
+    RATE_LIMITS: "rateLimits",
+    ROUTING_RULES: "routingRules",
+    CACHE_PERSIST: "cachePersist",
 } as const;
 
 // Generic storage functions
@@ -179,6 +196,84 @@ export const appStorage = {
     clearErrorLogs(): void {
         storage.remove(STORAGE_KEYS.ERROR_LOGS);
     },
+
+    // Provider base URL overrides (empty = built-in default endpoint)
+    getProviderBaseUrls(): Record<string, string> {
+        return storage.getJSON<Record<string, string>>(
+            STORAGE_KEYS.PROVIDER_BASE_URLS,
+            {}
+        );
+    },
+    getProviderBaseUrl(providerId: string): string {
+        return this.getProviderBaseUrls()[providerId] || "";
+    },
+    setProviderBaseUrls(urls: Record<string, string>): void {
+        storage.setJSON(STORAGE_KEYS.PROVIDER_BASE_URLS, urls);
+    },
+
+    // Per-provider credentials and options (DeepL, ...)
+    getProviderConfig(providerId: string): ProviderConfig {
+        const configs = storage.getJSON<Record<string, ProviderConfig>>(
+            STORAGE_KEYS.PROVIDER_CONFIGS,
+            {}
+        );
+        return configs[providerId] || {};
+    },
+    setProviderConfig(providerId: string, config: ProviderConfig): void {
+        const configs = storage.getJSON<Record<string, ProviderConfig>>(
+            STORAGE_KEYS.PROVIDER_CONFIGS,
+            {}
+        );
+        configs[providerId] = config;
+        storage.setJSON(STORAGE_KEYS.PROVIDER_CONFIGS, configs);
+    },
+
+    // Provider fallback chains per language pair
+    getFallbackChains(): FallbackChain[] {
+        return storage.getJSON<FallbackChain[]>(STORAGE_KEYS.FALLBACK_CHAINS, []);
+    },
+    setFallbackChains(chains: FallbackChain[]): void {
+        storage.setJSON(STORAGE_KEYS.FALLBACK_CHAINS, chains);
+    },
+
+    // Network (proxy / extra CA certificates)
+    getNetworkSettings(): NetworkSettings {
+        return storage.getJSON<NetworkSettings>(STORAGE_KEYS.NETWORK_SETTINGS, {
+            proxy_mode: "system",
+            ca_cert_paths: [],
+        });
+    },
+    setNetworkSettings(settings: NetworkSettings): void {
+        storage.setJSON(STORAGE_KEYS.NETWORK_SETTINGS, settings);
+    },
+
+    // Client-side rate limits (providers without an entry use the backend default)
+    getRateLimits(): Record<string, RateLimitConfig> {
+        return storage.getJSON<Record<string, RateLimitConfig>>(STORAGE_KEYS.RATE_LIMITS, {});
+    },
+    setRateLimits(limits: Record<string, RateLimitConfig>): void {
+        storage.setJSON(STORAGE_KEYS.RATE_LIMITS, limits);
+    },
+
+    // Translation cache on disk (off: the cache only lives in memory for the session)
+    getCachePersist(): boolean {
+        return storage.getBoolean(STORAGE_KEYS.CACHE_PERSIST, false);
+    },
+    setCachePersist(enabled: boolean): void {
+        storage.setBoolean(STORAGE_KEYS.CACHE_PERSIST, enabled);
+    },
+
+    // Language-pair routing (ja → en, everything else → the target language by default)
+    getRoutingSettings(): RoutingSettings {
+        return storage.getJSON<RoutingSettings>(STORAGE_KEYS.ROUTING_RULES, {
+            reverse_target: this.getTargetLanguage() === "ja" ? "en" : "ja",
+            reverse_when_same: true,
+            rules: [],
+        });
+    },
+    setRoutingSettings(settings: RoutingSettings): void {
+        storage.setJSON(STORAGE_KEYS.ROUTING_RULES, settings);
+    },
 };
 
 // Export keys for reference